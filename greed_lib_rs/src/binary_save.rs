@@ -0,0 +1,498 @@
+use super::{
+  Amount, BinaryFormatError, Challenge, Direction, FakeTile, GameField, GameMeta, Grid2D, Pos,
+  RuleSet, Seed, Size2D, TileGrid, Topology, TravelDistance, CURRENT_FILE_VERSION,
+};
+use alloc::{boxed::Box, string::String, vec::Vec};
+use bitflags::bitflags;
+use bitvec::{field::BitField, prelude as bv};
+
+/// Binary saves start with a zero byte which can never start a json or game field string.
+pub const BINARY_MAGIC: [u8; 3] = [0x00, b'G', b'R'];
+const BINARY_FORMAT_VERSION: u8 = 1;
+
+/// `topology` is stored as index + 1 into this array, 0 if it is missing.
+const TOPOLOGIES: [Topology; 2] = [Topology::Bounded, Topology::Torus];
+
+/// The travel distance of the `rule_set` is stored as index + 1 into this array,
+/// 0 if the rule set is missing.
+const TRAVEL_DISTANCES: [TravelDistance; 3] = [
+  TravelDistance::FirstTile,
+  TravelDistance::LastTile,
+  TravelDistance::SumMod9,
+];
+
+/// Board nibble of off-board cells of non-rectangular boards.
+const OFF_BOARD_NIBBLE: u8 = 0x0F;
+
+/// Bits used by one move: 3 for the direction and 4 for the amount.
+const MOVE_BITS: usize = 7;
+
+bitflags! {
+  /// Marks which optional fields follow the header.
+  #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+  struct Fields: u8 {
+    const SEED             = 0b0000_0001;
+    const NAME             = 0b0000_0010;
+    const UTC_STARTED_MS   = 0b0000_0100;
+    const UTC_FINISHED_MS  = 0b0000_1000;
+    /// The board is stored as tiles instead of being generated from the seed.
+    const GAME_FIELD       = 0b0001_0000;
+    const UNDOS            = 0b0010_0000;
+    const REDO_MOVES       = 0b0100_0000;
+    const MOVE_TIMESTAMPS  = 0b1000_0000;
+  }
+}
+
+impl GameMeta {
+  /// Encodes the meta data in the compact binary format.
+  ///
+  /// All numbers are LEB128 varints. Signed numbers are zigzag encoded first.
+  ///
+  /// Layout:
+  /// * `BINARY_MAGIC`, format version byte, `Fields` byte, topology byte
+  /// * The travel distance byte of the `rule_set`. If it isn't 0 a byte with one bit per enabled
+  ///   slot in `Direction::ALL_DIRECTIONS_CW` order follows and then the x and y of every enabled vector.
+  /// * `greed_version` (0 if missing), `time_spent_ms`
+  /// * `seed`, `name` as length prefixed UTF-8 if present
+  /// * `utc_started_ms`, `utc_finished_ms` if present
+  /// * The board if it can't be generated from the seed:
+  ///   `x_size`, `y_size`, player index and one 4-bit nibble per tile (low nibble first).
+  ///   Nibbles are the amount, `Tile::Wall` for walls or 15 for off-board cells.
+  /// * `undos` if present
+  /// * `moves` and `redo_moves` (if present) as count followed by the packed moves.
+  ///   Every move is a 3-bit index into `Direction::ALL_DIRECTIONS_CW` and a 4-bit amount.
+  /// * `move_timestamps_ms` if present as count followed by the differences between them
+  /// * The clock: a byte with bit 0 for `paused` and bit 1 for a `time_limit_ms`,
+  ///   `idle_ms` and the `time_limit_ms` if present
+  /// * `challenge`: 0 if missing, otherwise 1 followed by the id as length prefixed UTF-8
+  ///
+  /// The derived fields `score`, `human_score` and `last_game_field` are not stored.
+  /// Neither is the `signature`, since it covers the derived fields.
+  /// They are restored by replaying the moves when the game is loaded.
+  /// The `last_game_field` can't be stored as board,
+  /// since the moves are always replayed on top of the stored board.
+  ///
+  /// # Errors
+  /// * If a move has an invalid direction.
+  /// * If there is neither a `seed` nor an `initial_game_field`.
+  pub fn to_bytes(&self) -> Result<Vec<u8>, BinaryFormatError> {
+    if self.seed.is_none() && self.initial_game_field.is_none() {
+      return Err(BinaryFormatError::MissingInitialGameField);
+    }
+    let board = self.initial_game_field.as_ref().filter(|game_field| {
+      self
+        .seed
+        .as_ref()
+        .is_none_or(|seed| GameField::from_seed(seed) != **game_field)
+    });
+
+    let mut fields = Fields::empty();
+    fields.set(Fields::SEED, self.seed.is_some());
+    fields.set(Fields::NAME, self.name.is_some());
+    fields.set(Fields::UTC_STARTED_MS, self.utc_started_ms.is_some());
+    fields.set(Fields::UTC_FINISHED_MS, self.utc_finished_ms.is_some());
+    fields.set(Fields::GAME_FIELD, board.is_some());
+    fields.set(Fields::UNDOS, self.undos.is_some());
+    fields.set(Fields::REDO_MOVES, self.redo_moves.is_some());
+    fields.set(Fields::MOVE_TIMESTAMPS, self.move_timestamps_ms.is_some());
+
+    let mut writer = Writer::default();
+    writer.bytes.extend_from_slice(&BINARY_MAGIC);
+    writer.bytes.push(BINARY_FORMAT_VERSION);
+    writer.bytes.push(fields.bits());
+    writer.bytes.push(self.topology.map_or(0, |topology| {
+      #[allow(clippy::cast_possible_truncation)] // There are only a few topologies
+      let index = TOPOLOGIES
+        .iter()
+        .position(|&it| it == topology)
+        .unwrap_or(0) as u8;
+      index + 1
+    }));
+    writer.rule_set(self.rule_set.as_ref());
+    writer.varint(self.greed_version.unwrap_or(0));
+    writer.signed(self.time_spent_ms);
+    if let Some(seed) = &self.seed {
+      writer.str(&String::from(seed));
+    }
+    if let Some(name) = &self.name {
+      writer.str(name);
+    }
+    if let Some(utc_started_ms) = self.utc_started_ms {
+      writer.signed(utc_started_ms);
+    }
+    if let Some(utc_finished_ms) = self.utc_finished_ms {
+      writer.signed(utc_finished_ms);
+    }
+    if let Some(board) = board {
+      writer.game_field(board);
+    }
+    if let Some(undos) = self.undos {
+      writer.usize(undos);
+    }
+    writer.moves(self.moves.as_deref().unwrap_or_default())?;
+    if let Some(redo_moves) = &self.redo_moves {
+      writer.moves(redo_moves)?;
+    }
+    if let Some(move_timestamps_ms) = &self.move_timestamps_ms {
+      writer.usize(move_timestamps_ms.len());
+      let mut previous = 0;
+      for &timestamp in move_timestamps_ms {
+        writer.signed(timestamp.wrapping_sub(previous));
+        previous = timestamp;
+      }
+    }
+    writer
+      .bytes
+      .push(u8::from(self.paused) | u8::from(self.time_limit_ms.is_some()) << 1);
+    writer.signed(self.idle_ms);
+    if let Some(time_limit_ms) = self.time_limit_ms {
+      writer.signed(time_limit_ms);
+    }
+    writer.bytes.push(u8::from(self.challenge.is_some()));
+    if let Some(challenge) = &self.challenge {
+      writer.str(&String::from(challenge));
+    }
+    Ok(writer.bytes)
+  }
+
+  /// Decodes meta data encoded with `to_bytes`.
+  /// # Errors
+  /// If `bytes` isn't a valid binary save.
+  pub fn from_bytes(bytes: &[u8]) -> Result<Self, BinaryFormatError> {
+    let mut reader = Reader { bytes };
+    if reader.take(BINARY_MAGIC.len())? != BINARY_MAGIC {
+      return Err(BinaryFormatError::MissingMagic);
+    }
+    let version = reader.byte()?;
+    if version != BINARY_FORMAT_VERSION {
+      return Err(BinaryFormatError::UnsupportedVersion {
+        found: version,
+        supported: BINARY_FORMAT_VERSION,
+      });
+    }
+    let fields = Fields::from_bits_retain(reader.byte()?);
+    let topology = reader.topology()?;
+    let rule_set = reader.rule_set()?;
+
+    let greed_version = Some(reader.varint()?).filter(|&greed_version| greed_version != 0);
+    let time_spent_ms = reader.signed()?;
+    let seed = fields
+      .contains(Fields::SEED)
+      .then(|| Seed::try_from(reader.string()?).map_err(BinaryFormatError::from))
+      .transpose()?;
+    let name = fields
+      .contains(Fields::NAME)
+      .then(|| reader.string())
+      .transpose()?;
+    let utc_started_ms = fields
+      .contains(Fields::UTC_STARTED_MS)
+      .then(|| reader.signed())
+      .transpose()?;
+    let utc_finished_ms = fields
+      .contains(Fields::UTC_FINISHED_MS)
+      .then(|| reader.signed())
+      .transpose()?;
+    let initial_game_field = fields
+      .contains(Fields::GAME_FIELD)
+      .then(|| reader.game_field())
+      .transpose()?;
+    let undos = fields
+      .contains(Fields::UNDOS)
+      .then(|| reader.usize())
+      .transpose()?;
+    let moves = reader.moves()?;
+    let redo_moves = fields
+      .contains(Fields::REDO_MOVES)
+      .then(|| reader.moves())
+      .transpose()?;
+    let move_timestamps_ms = fields
+      .contains(Fields::MOVE_TIMESTAMPS)
+      .then(|| reader.timestamps())
+      .transpose()?;
+    let (paused, idle_ms, time_limit_ms) = reader.clock()?;
+    let challenge = reader.challenge()?;
+
+    if !reader.bytes.is_empty() {
+      return Err(BinaryFormatError::TrailingBytes {
+        count: reader.bytes.len(),
+      });
+    }
+
+    Ok(Self {
+      file_version: Some(CURRENT_FILE_VERSION),
+      greed_version,
+      seed,
+      challenge,
+      topology,
+      rule_set,
+      name,
+      utc_started_ms,
+      utc_finished_ms,
+      time_spent_ms,
+      idle_ms,
+      paused,
+      time_limit_ms,
+      moves: Some(moves),
+      redo_moves,
+      score: None,
+      human_score: None,
+      undos,
+      move_timestamps_ms,
+      initial_game_field,
+      last_game_field: None,
+      signature: None,
+    })
+  }
+}
+
+#[derive(Default)]
+struct Writer {
+  bytes: Vec<u8>,
+}
+
+impl Writer {
+  fn varint(&mut self, mut value: u64) {
+    while value >= 0x80 {
+      #[allow(clippy::cast_possible_truncation)] // Only the lowest 7 bits are used
+      self.bytes.push(value as u8 | 0x80);
+      value >>= 7;
+    }
+    #[allow(clippy::cast_possible_truncation)] // value < 0x80
+    self.bytes.push(value as u8);
+  }
+
+  fn usize(&mut self, value: usize) {
+    self.varint(value as u64);
+  }
+
+  #[allow(clippy::cast_sign_loss)] // Zigzag encoding
+  fn signed(&mut self, value: i64) {
+    self.varint(((value << 1) ^ (value >> 63)) as u64);
+  }
+
+  fn str(&mut self, str: &str) {
+    self.usize(str.len());
+    self.bytes.extend_from_slice(str.as_bytes());
+  }
+
+  fn rule_set(&mut self, rule_set: Option<&RuleSet>) {
+    let Some(rule_set) = rule_set else {
+      self.bytes.push(0);
+      return;
+    };
+    #[allow(clippy::cast_possible_truncation)] // There are only a few travel distances
+    let index = TRAVEL_DISTANCES
+      .iter()
+      .position(|&it| it == rule_set.travel_distance())
+      .unwrap_or(0) as u8;
+    self.bytes.push(index + 1);
+    let enabled = rule_set
+      .vectors()
+      .iter()
+      .enumerate()
+      .filter(|(_, vector)| vector.is_some())
+      .fold(0, |enabled, (slot, _)| enabled | 1 << slot);
+    self.bytes.push(enabled);
+    for vector in rule_set.vectors().iter().flatten() {
+      self.signed(vector.x as i64);
+      self.signed(vector.y as i64);
+    }
+  }
+
+  fn game_field(&mut self, game_field: &GameField) {
+    let Size2D { x_size, y_size } = game_field.dimensions();
+    self.usize(x_size);
+    self.usize(y_size);
+    self.usize(game_field.pos_to_index_unchecked(game_field.player_pos()));
+    let nibble = |index: usize| {
+      if game_field.is_playable(game_field.index_to_pos_unchecked(index)) {
+        u8::from(game_field.vec[index])
+      } else {
+        OFF_BOARD_NIBBLE
+      }
+    };
+    for index in (0..game_field.cell_count()).step_by(2) {
+      let low = nibble(index);
+      let high = game_field.valid_index(index + 1).map_or(0, nibble);
+      self.bytes.push(low | high << 4);
+    }
+  }
+
+  fn moves(&mut self, moves: &[(Direction, Amount)]) -> Result<(), BinaryFormatError> {
+    self.usize(moves.len());
+    let mut bits: bv::BitVec<u8, bv::Lsb0> = bv::BitVec::with_capacity(moves.len() * MOVE_BITS);
+    for &(dir, amount) in moves {
+      let dir_index = Direction::ALL_DIRECTIONS_CW
+        .into_iter()
+        .position(|valid_dir| valid_dir == dir.reduce())
+        .ok_or(BinaryFormatError::InvalidDirection)?;
+      for bit in 0..3 {
+        bits.push(dir_index >> bit & 1 == 1);
+      }
+      for bit in 0..4 {
+        bits.push(amount.amount() >> bit & 1 == 1);
+      }
+    }
+    self.bytes.extend_from_slice(bits.as_raw_slice());
+    Ok(())
+  }
+}
+
+struct Reader<'a> {
+  bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+  fn take(&mut self, count: usize) -> Result<&'a [u8], BinaryFormatError> {
+    if count > self.bytes.len() {
+      return Err(BinaryFormatError::UnexpectedEnd);
+    }
+    let (taken, rest) = self.bytes.split_at(count);
+    self.bytes = rest;
+    Ok(taken)
+  }
+
+  fn byte(&mut self) -> Result<u8, BinaryFormatError> {
+    Ok(self.take(1)?[0])
+  }
+
+  fn varint(&mut self) -> Result<u64, BinaryFormatError> {
+    let mut value = 0;
+    for shift in (0..64).step_by(7) {
+      let byte = self.byte()?;
+      let bits = u64::from(byte & 0x7F);
+      if bits << shift >> shift != bits {
+        return Err(BinaryFormatError::NumberOutOfRange);
+      }
+      value |= bits << shift;
+      if byte & 0x80 == 0 {
+        return Ok(value);
+      }
+    }
+    Err(BinaryFormatError::NumberOutOfRange)
+  }
+
+  fn usize(&mut self) -> Result<usize, BinaryFormatError> {
+    usize::try_from(self.varint()?).map_err(|_| BinaryFormatError::NumberOutOfRange)
+  }
+
+  #[allow(clippy::cast_possible_wrap)] // Zigzag decoding
+  fn signed(&mut self) -> Result<i64, BinaryFormatError> {
+    let value = self.varint()?;
+    Ok((value >> 1) as i64 ^ -((value & 1) as i64))
+  }
+
+  fn string(&mut self) -> Result<String, BinaryFormatError> {
+    let len = self.usize()?;
+    let bytes = self.take(len)?;
+    let str = core::str::from_utf8(bytes).map_err(|_| BinaryFormatError::InvalidUtf8)?;
+    Ok(String::from(str))
+  }
+
+  fn topology(&mut self) -> Result<Option<Topology>, BinaryFormatError> {
+    match self.byte()? {
+      0 => Ok(None),
+      found => TOPOLOGIES
+        .get(usize::from(found) - 1)
+        .copied()
+        .map(Some)
+        .ok_or(BinaryFormatError::InvalidTopology { found }),
+    }
+  }
+
+  /// Count followed by the differences between the timestamps
+  fn timestamps(&mut self) -> Result<Vec<i64>, BinaryFormatError> {
+    let count = self.usize()?;
+    let mut previous: i64 = 0;
+    (0..count)
+      .map(|_| {
+        previous = previous.wrapping_add(self.signed()?);
+        Ok(previous)
+      })
+      .collect()
+  }
+
+  fn challenge(&mut self) -> Result<Option<Challenge>, BinaryFormatError> {
+    match self.byte()? {
+      0 => Ok(None),
+      _ => Ok(Some(Challenge::try_from(self.string()?)?)),
+    }
+  }
+
+  /// `paused`, `idle_ms` and `time_limit_ms`
+  fn clock(&mut self) -> Result<(bool, i64, Option<i64>), BinaryFormatError> {
+    let clock = self.byte()?;
+    let idle_ms = self.signed()?;
+    let time_limit_ms = (clock & 0b10 != 0).then(|| self.signed()).transpose()?;
+    Ok((clock & 0b01 != 0, idle_ms, time_limit_ms))
+  }
+
+  fn rule_set(&mut self) -> Result<Option<RuleSet>, BinaryFormatError> {
+    let travel_distance = match self.byte()? {
+      0 => return Ok(None),
+      found => *TRAVEL_DISTANCES
+        .get(usize::from(found) - 1)
+        .ok_or(BinaryFormatError::InvalidTravelDistance { found })?,
+    };
+    let enabled = self.byte()?;
+    let mut vectors = [None; 8];
+    for (slot, vector) in vectors.iter_mut().enumerate() {
+      if enabled >> slot & 1 == 1 {
+        let x = self.signed()?;
+        let y = self.signed()?;
+        let coordinate =
+          |value| isize::try_from(value).map_err(|_| BinaryFormatError::NumberOutOfRange);
+        *vector = Some(Pos::new(coordinate(x)?, coordinate(y)?));
+      }
+    }
+    Ok(Some(
+      RuleSet::try_custom(vectors)?.with_travel_distance(travel_distance),
+    ))
+  }
+
+  fn game_field(&mut self) -> Result<GameField, BinaryFormatError> {
+    let x_size = self.usize()?;
+    let y_size = self.usize()?;
+    let size = Size2D::new(x_size, y_size)?;
+    let player_pos = size
+      .index_to_pos(self.usize()?)
+      .ok_or(BinaryFormatError::InvalidPlayerPos)?;
+    let cell_count = size.cell_count();
+    let packed = self.take(cell_count.div_ceil(2))?;
+    let nibbles = (0..cell_count).map(|index| packed[index / 2] >> (index % 2 * 4) & 0x0F);
+    let playable = nibbles
+      .clone()
+      .map(|nibble| nibble != OFF_BOARD_NIBBLE)
+      .collect();
+    let vec = nibbles
+      .map(|nibble| match nibble {
+        OFF_BOARD_NIBBLE => Ok(FakeTile::WALL),
+        nibble => {
+          FakeTile::try_from(nibble).map_err(|_| BinaryFormatError::InvalidTile { found: nibble })
+        },
+      })
+      .collect::<Result<Box<_>, _>>()?;
+    GameField::from_parts(vec, size, player_pos)
+      .and_then(|game_field| game_field.with_playable(playable))
+      .ok_or(BinaryFormatError::InvalidPlayerPos)
+  }
+
+  fn moves(&mut self) -> Result<Vec<(Direction, Amount)>, BinaryFormatError> {
+    let count = self.usize()?;
+    let bit_count = count
+      .checked_mul(MOVE_BITS)
+      .ok_or(BinaryFormatError::NumberOutOfRange)?;
+    let packed = self.take(bit_count.div_ceil(8))?;
+    let bits = bv::BitSlice::<u8, bv::Lsb0>::from_slice(packed);
+    bits[..bit_count]
+      .chunks_exact(MOVE_BITS)
+      .map(|move_bits| {
+        let dir_index = move_bits[..3].load_le::<usize>();
+        let amount = move_bits[3..].load_le::<u8>();
+        let amount = Amount::try_from(amount)
+          .map_err(|_| BinaryFormatError::InvalidAmount { found: amount })?;
+        Ok((Direction::ALL_DIRECTIONS_CW[dir_index], amount))
+      })
+      .collect()
+  }
+}
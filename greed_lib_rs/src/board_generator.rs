@@ -0,0 +1,296 @@
+use super::{
+  Direction, FakeTile, GameField, Grid2D, Pos, RuleSet, Size2D, TileChooser, TileProbs,
+  TravelDistance,
+};
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
+use core::{
+  cmp::Reverse,
+  fmt::{self, Display, Formatter},
+};
+use rand::prelude::*;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+#[error("Unknown board generator ({found}) expected 'uniform', 'clustered', 'symmetric' or 'high_clearance'")]
+pub struct BoardGeneratorIdConversionError {
+  pub found: String,
+}
+
+/// Fills a board with tiles and picks the start of the player.
+///
+/// Generators must only draw from `rng`, so the same seed always produces the same board.
+pub trait BoardGenerator {
+  fn generate(&self, size: Size2D, tile_probs: &TileProbs, rng: &mut dyn RngCore) -> GameField;
+}
+
+/// Selects the `BoardGenerator` of a `Seed`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BoardGeneratorId {
+  #[default]
+  Uniform,
+  Clustered,
+  Symmetric,
+  HighClearance,
+}
+
+impl BoardGeneratorId {
+  pub const ALL: [Self; 4] = [
+    Self::Uniform,
+    Self::Clustered,
+    Self::Symmetric,
+    Self::HighClearance,
+  ];
+
+  #[must_use]
+  pub const fn name(self) -> &'static str {
+    match self {
+      Self::Uniform => "uniform",
+      Self::Clustered => "clustered",
+      Self::Symmetric => "symmetric",
+      Self::HighClearance => "high_clearance",
+    }
+  }
+
+  #[must_use]
+  pub const fn generator(self) -> &'static dyn BoardGenerator {
+    match self {
+      Self::Uniform => &UniformGenerator,
+      Self::Clustered => &ClusteredGenerator,
+      Self::Symmetric => &SymmetricGenerator,
+      Self::HighClearance => &HighClearanceGenerator,
+    }
+  }
+}
+
+impl Display for BoardGeneratorId {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    f.write_str(self.name())
+  }
+}
+
+impl TryFrom<&str> for BoardGeneratorId {
+  type Error = BoardGeneratorIdConversionError;
+
+  fn try_from(value: &str) -> Result<Self, Self::Error> {
+    Self::ALL
+      .into_iter()
+      .find(|generator| generator.name() == value)
+      .ok_or_else(|| BoardGeneratorIdConversionError {
+        found: value.into(),
+      })
+  }
+}
+
+#[allow(clippy::cast_possible_wrap)] // Can never wrap since Size2D contains limited sizes.
+fn random_pos(rng: &mut dyn RngCore, size: Size2D) -> Pos {
+  let x = rng.gen_range(0..size.x_size) as isize;
+  let y = rng.gen_range(0..size.y_size) as isize;
+  Pos::new(x, y)
+}
+
+/// Chooses every tile independently. The classic boards.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct UniformGenerator;
+
+impl BoardGenerator for UniformGenerator {
+  fn generate(&self, size: Size2D, tile_probs: &TileProbs, rng: &mut dyn RngCore) -> GameField {
+    let mut tile_chooser = TileChooser::new(rng, tile_probs);
+    let vec = (0..size.cell_count())
+      .map(|_| tile_chooser.choose())
+      .collect();
+    let player_pos = random_pos(tile_chooser.rng, size);
+    GameField::new_generated(vec, size, player_pos)
+  }
+}
+
+/// Groups equal tiles into blobs of roughly `CLUSTER_SIZE` x `CLUSTER_SIZE` tiles.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ClusteredGenerator;
+
+impl ClusteredGenerator {
+  pub const CLUSTER_SIZE: usize = 4;
+}
+
+impl BoardGenerator for ClusteredGenerator {
+  fn generate(&self, size: Size2D, tile_probs: &TileProbs, rng: &mut dyn RngCore) -> GameField {
+    const CLUSTER_SIZE: usize = ClusteredGenerator::CLUSTER_SIZE;
+    let mut tile_chooser = TileChooser::new(rng, tile_probs);
+    // One tile per cluster, the boundaries between clusters are jittered
+    let clusters_x = size.x_size / CLUSTER_SIZE + 1;
+    let clusters_y = size.y_size / CLUSTER_SIZE + 1;
+    let clusters: Vec<_> = (0..clusters_x * clusters_y)
+      .map(|_| tile_chooser.choose())
+      .collect();
+    let vec = (0..size.cell_count())
+      .map(|index| {
+        // A quarter of the tiles is noise
+        if !tile_chooser.rng.gen_ratio(3, 4) {
+          return tile_chooser.choose();
+        }
+        let x = (index % size.x_size + tile_chooser.rng.gen_range(0..CLUSTER_SIZE)) / CLUSTER_SIZE;
+        let y = (index / size.x_size + tile_chooser.rng.gen_range(0..CLUSTER_SIZE)) / CLUSTER_SIZE;
+        clusters[y * clusters_x + x]
+      })
+      .collect();
+    let player_pos = random_pos(tile_chooser.rng, size);
+    GameField::new_generated(vec, size, player_pos)
+  }
+}
+
+/// Point symmetric boards. Rotating the board by 180 degrees doesn't change any tile
+/// except the player, whose mirrored tile is empty too.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SymmetricGenerator;
+
+impl BoardGenerator for SymmetricGenerator {
+  fn generate(&self, size: Size2D, tile_probs: &TileProbs, rng: &mut dyn RngCore) -> GameField {
+    let mut tile_chooser = TileChooser::new(rng, tile_probs);
+    let cell_count = size.cell_count();
+    let half: Vec<_> = (0..cell_count.div_ceil(2))
+      .map(|_| tile_chooser.choose())
+      .collect();
+    let mut vec: Box<_> = (0..cell_count)
+      .map(|index| half[index.min(cell_count - 1 - index)])
+      .collect();
+    let player_pos = random_pos(tile_chooser.rng, size);
+    vec[cell_count - 1 - size.pos_to_index_unchecked(player_pos)] = FakeTile::EMTPY;
+    GameField::new_generated(vec, size, player_pos)
+  }
+}
+
+/// Plants a long path of valid moves starting at the player and fills the rest of the board
+/// like `UniformGenerator`. Following the path always clears all of its tiles.
+///
+/// Random walks are tried until one clears at least `MIN_CLEARANCE_PERCENT` of the board.
+/// If none of `MAX_ATTEMPTS` walks does the longest one is planted.
+/// The path uses `RuleSet::EIGHT_WAY` with `TravelDistance::FirstTile`.
+/// Distances are weighted by the tile probabilities.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HighClearanceGenerator;
+
+impl HighClearanceGenerator {
+  pub const MIN_CLEARANCE_PERCENT: usize = 75;
+  pub const MAX_ATTEMPTS: usize = 16;
+
+  /// Like `generate` but also returns the planted path.
+  #[must_use]
+  pub fn generate_with_path(
+    size: Size2D,
+    tile_probs: &TileProbs,
+    rng: &mut dyn RngCore,
+  ) -> (GameField, Vec<Direction>) {
+    let mut tile_chooser = TileChooser::new(rng, tile_probs);
+    let mut vec: Box<_> = (0..size.cell_count())
+      .map(|_| tile_chooser.choose())
+      .collect();
+    let player_pos = random_pos(tile_chooser.rng, size);
+
+    let min_clearance = size.cell_count() * Self::MIN_CLEARANCE_PERCENT / 100;
+    let mut path = Vec::new();
+    let mut path_len = 0;
+    for _ in 0..Self::MAX_ATTEMPTS {
+      let walk = Self::random_walk(size, tile_probs, player_pos, tile_chooser.rng);
+      let walk_len = walk.iter().map(|&(_, distance)| distance).sum();
+      if walk_len > path_len {
+        path = walk;
+        path_len = walk_len;
+      }
+      if path_len >= min_clearance {
+        break;
+      }
+    }
+
+    // The consumed tiles after the first one of a move may be any amount
+    let amounts = tile_probs.clone().with_wall(0);
+    let mut amount_chooser = TileChooser::new(tile_chooser.rng, &amounts);
+    let mut pos = player_pos;
+    for &(dir, distance) in &path {
+      for step in 1..=distance {
+        // The walk only contains moves inside the board
+        pos += dir;
+        vec[size.pos_to_index_unchecked(pos)] = if step == 1 {
+          #[allow(clippy::cast_possible_truncation)] // At most 9
+          FakeTile::from_unchecked_u8(distance as u8)
+        } else {
+          amount_chooser.choose()
+        };
+      }
+    }
+    let directions = path.into_iter().map(|(dir, _)| dir).collect();
+    (GameField::new_generated(vec, size, player_pos), directions)
+  }
+
+  /// Moves as direction and distance. No tile is visited twice.
+  ///
+  /// Every direction gets a random distance and the move that leaves the fewest onward moves
+  /// is taken (Warnsdorff's rule), which avoids cutting off parts of the board.
+  fn random_walk(
+    size: Size2D,
+    tile_probs: &TileProbs,
+    start: Pos,
+    rng: &mut dyn RngCore,
+  ) -> Vec<(Direction, usize)> {
+    // Distances are weighted like the tiles, walls are only placed off the path
+    let probs = tile_probs.tile_weights();
+    let mut visited = vec![false; size.cell_count()];
+    visited[size.pos_to_index_unchecked(start)] = true;
+    // Amount of free tiles in `dir` up to the maximum distance
+    let free = |visited: &[bool], pos: Pos, dir: Direction| {
+      core::iter::successors(Some(pos), |&pos| size.neighbor(pos, dir))
+        .skip(1)
+        .take(TravelDistance::MAX_DISTANCE)
+        .take_while(|&pos| !visited[size.pos_to_index_unchecked(pos)])
+        .count()
+    };
+    let mut pos = start;
+    let mut walk = Vec::new();
+    loop {
+      let mut best = None;
+      for dir in RuleSet::EIGHT_WAY.directions() {
+        let free_tiles = free(&visited, pos, dir);
+        let mut target = pos;
+        for distance in 1..=free_tiles {
+          target += dir;
+          visited[size.pos_to_index_unchecked(target)] = true;
+          let onward_moves = RuleSet::EIGHT_WAY
+            .directions()
+            .filter(|&dir| free(&visited, target, dir) != 0)
+            .count();
+          // Ties are broken randomly, weighted by the tile probabilities
+          let weight = u32::from(probs[distance - 1]);
+          let rank = (
+            onward_moves == 0,
+            onward_moves,
+            Reverse(rng.gen_range(0..=weight * 0x00FF_FFFF)),
+          );
+          if best
+            .as_ref()
+            .is_none_or(|(best_rank, _, _)| rank < *best_rank)
+          {
+            best = Some((rank, dir, distance));
+          }
+        }
+        // Undo the trial moves
+        for _ in 0..free_tiles {
+          visited[size.pos_to_index_unchecked(target)] = false;
+          target -= dir;
+        }
+      }
+      let Some((_, dir, distance)) = best else {
+        return walk;
+      };
+      for _ in 0..distance {
+        // Only free tiles inside the board are options
+        pos += dir;
+        visited[size.pos_to_index_unchecked(pos)] = true;
+      }
+      walk.push((dir, distance));
+    }
+  }
+}
+
+impl BoardGenerator for HighClearanceGenerator {
+  fn generate(&self, size: Size2D, tile_probs: &TileProbs, rng: &mut dyn RngCore) -> GameField {
+    Self::generate_with_path(size, tile_probs, rng).0
+  }
+}
@@ -0,0 +1,219 @@
+use super::{Seed, SharedClock, Size2D, TileProbs, UserString, UserStringError};
+use alloc::{format, string::String};
+use chrono::{Datelike, NaiveDate, Weekday};
+use core::fmt::{self, Display, Formatter};
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ChallengeConversionError {
+  #[error("Invalid challenge ({found}) expected 'daily-YYYY-MM-DD' or 'weekly-YYYY-Www'")]
+  InvalidFormat { found: String },
+  #[error("Invalid salt")]
+  InvalidSalt {
+    #[from]
+    source: UserStringError,
+  },
+}
+
+/// How long a `Challenge` lasts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChallengePeriod {
+  /// A board with the default size and tile probabilities every day.
+  Daily,
+  /// A board from `Challenge::WEEKLY_PRESETS` every ISO week.
+  Weekly,
+}
+
+/// A board everyone plays on the same day or week.
+///
+/// The id is `daily-<YYYY-MM-DD>` or `weekly-<YYYY>-W<ww>` (ISO week) optionally followed by
+/// `@<salt>`. Teams can use their own salt so their board differs from everyone else's.
+///
+/// Dates are UTC dates, so everyone gets the same board regardless of their time zone.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String")]
+#[serde(into = "String")]
+pub struct Challenge {
+  period: ChallengePeriod,
+  /// The first day of the period.
+  date: NaiveDate,
+  salt: Option<UserString>,
+}
+
+impl Challenge {
+  /// The size and tile probabilities of the weekly challenges.
+  /// ISO week `w` uses `WEEKLY_PRESETS[(w - 1) % WEEKLY_PRESETS.len()]`.
+  pub const WEEKLY_PRESETS: [(Size2D, TileProbs); 4] = [
+    // Large board
+    (
+      Size2D::new_unchecked(120, 30),
+      TileProbs::DEFAULT_TILE_PROBABILITIES,
+    ),
+    // Mostly short moves
+    (
+      Size2D::DEFAULT_SIZE,
+      TileProbs::new_unchecked([9, 8, 7, 6, 5, 4, 3, 2, 1]),
+    ),
+    // Walls
+    (
+      Size2D::DEFAULT_SIZE,
+      TileProbs::DEFAULT_TILE_PROBABILITIES.with_wall(1),
+    ),
+    // Mostly long jumps on a small board
+    (
+      Size2D::new_unchecked(40, 15),
+      TileProbs::new_unchecked([1, 2, 3, 4, 5, 6, 7, 8, 9]),
+    ),
+  ];
+
+  #[must_use]
+  pub const fn daily(date: NaiveDate) -> Self {
+    Self {
+      period: ChallengePeriod::Daily,
+      date,
+      salt: None,
+    }
+  }
+
+  /// The challenge of the ISO week containing `date`.
+  #[must_use]
+  pub fn weekly(date: NaiveDate) -> Self {
+    Self {
+      period: ChallengePeriod::Weekly,
+      date: date.week(Weekday::Mon).first_day(),
+      salt: None,
+    }
+  }
+
+  /// The daily challenge of the current UTC date of `clock`.
+  #[must_use]
+  pub fn today(clock: &SharedClock) -> Self {
+    Self::daily(clock.now().date_naive())
+  }
+
+  /// The weekly challenge of the current UTC date of `clock`.
+  #[must_use]
+  pub fn this_week(clock: &SharedClock) -> Self {
+    Self::weekly(clock.now().date_naive())
+  }
+
+  /// Namespaces the challenge. Challenges with different salts have unrelated boards.
+  #[must_use]
+  pub fn with_salt(mut self, salt: UserString) -> Self {
+    self.salt = Some(salt);
+    self
+  }
+
+  #[must_use]
+  pub const fn period(&self) -> ChallengePeriod {
+    self.period
+  }
+
+  /// The first day of the challenge.
+  #[must_use]
+  pub const fn date(&self) -> NaiveDate {
+    self.date
+  }
+
+  #[must_use]
+  pub fn salt(&self) -> Option<&str> {
+    self.salt.as_ref().map(UserString::as_str)
+  }
+
+  /// The seed of the board. Only depends on the id of the challenge.
+  #[must_use = "the seed is hashed from the id on every call"]
+  pub fn seed(&self) -> Seed {
+    let (size, tile_probs) = match self.period {
+      ChallengePeriod::Daily => (Size2D::DEFAULT_SIZE, TileProbs::DEFAULT_TILE_PROBABILITIES),
+      ChallengePeriod::Weekly => {
+        let week = self.date.iso_week().week0() as usize;
+        Self::WEEKLY_PRESETS[week % Self::WEEKLY_PRESETS.len()].clone()
+      },
+    };
+    let mut hasher = Sha256::new();
+    hasher.update(format!("{self}"));
+    hasher.update("#challenge");
+    let hash = hasher.finalize();
+    let mut used_hash = [0; 16];
+    used_hash.copy_from_slice(&hash[0..16]);
+    let mut rng = rand_pcg::Pcg64Mcg::from_seed(used_hash);
+    Seed::new_random_with_rng(size, Some(tile_probs), &mut rng)
+  }
+
+  fn parse_date(period: &str) -> Option<(ChallengePeriod, NaiveDate)> {
+    if let Some(date) = period.strip_prefix("daily-") {
+      let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+      Some((ChallengePeriod::Daily, date))
+    } else {
+      let (year, week) = period.strip_prefix("weekly-")?.split_once("-W")?;
+      if week.len() != 2 {
+        return None;
+      }
+      let date = NaiveDate::from_isoywd_opt(year.parse().ok()?, week.parse().ok()?, Weekday::Mon)?;
+      Some((ChallengePeriod::Weekly, date))
+    }
+  }
+}
+
+impl Display for Challenge {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self.period {
+      ChallengePeriod::Daily => write!(
+        f,
+        "daily-{:04}-{:02}-{:02}",
+        self.date.year(),
+        self.date.month(),
+        self.date.day()
+      )?,
+      ChallengePeriod::Weekly => {
+        let week = self.date.iso_week();
+        write!(f, "weekly-{:04}-W{:02}", week.year(), week.week())?;
+      },
+    }
+    if let Some(salt) = &self.salt {
+      write!(f, "@{salt}")?;
+    }
+    Ok(())
+  }
+}
+
+impl From<&Challenge> for String {
+  fn from(challenge: &Challenge) -> Self {
+    format!("{challenge}")
+  }
+}
+impl From<Challenge> for String {
+  fn from(challenge: Challenge) -> Self {
+    format!("{challenge}")
+  }
+}
+
+impl TryFrom<&str> for Challenge {
+  type Error = ChallengeConversionError;
+
+  fn try_from(value: &str) -> Result<Self, Self::Error> {
+    let (period, salt) = value
+      .split_once('@')
+      .map_or((value, None), |(period, salt)| (period, Some(salt)));
+    let (period, date) =
+      Self::parse_date(period).ok_or_else(|| ChallengeConversionError::InvalidFormat {
+        found: value.into(),
+      })?;
+    Ok(Self {
+      period,
+      date,
+      salt: salt.map(UserString::try_from).transpose()?,
+    })
+  }
+}
+
+impl TryFrom<String> for Challenge {
+  type Error = ChallengeConversionError;
+
+  fn try_from(value: String) -> Result<Self, Self::Error> {
+    Self::try_from(value.as_str())
+  }
+}
@@ -0,0 +1,44 @@
+use super::{Amount, Direction, GameField, Pos};
+use alloc::{sync::Arc, vec::Vec};
+
+/// A move that was already validated by `Playable::checked_move`.
+/// It can be executed with `Playable::commit_move` without walking the move again.
+///
+/// The move is bound to the game state it was checked against.
+/// Committing it to any other state (or after the state changed) fails with `PlayableError::StaleCheckedMove`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[must_use]
+pub struct CheckedMove {
+  pub(super) dir: Direction,
+  /// Same as the return of `check_move`
+  pub(super) indices: Vec<usize>,
+  /// The player pos and game field at the time the move was checked.
+  /// `None` if it was checked by the default `Playable::checked_move`.
+  pub(super) origin: Option<(Pos, Arc<GameField>)>,
+}
+
+impl CheckedMove {
+  #[must_use]
+  pub const fn dir(&self) -> Direction {
+    self.dir
+  }
+
+  /// The indices that would be consumed. See `Playable::check_move`.
+  #[must_use]
+  pub fn indices(&self) -> &[usize] {
+    &self.indices
+  }
+
+  #[must_use]
+  pub fn amount(&self) -> Amount {
+    #[allow(clippy::cast_possible_truncation)]
+    // indices.len() can never be bigger than 9 since it originates from a loop over ammount.
+    Amount::new_unchecked(self.indices.len() as u8)
+  }
+
+  /// Consumes the move and returns the indices that would be consumed.
+  #[must_use]
+  pub fn into_indices(self) -> Vec<usize> {
+    self.indices
+  }
+}
@@ -0,0 +1,123 @@
+use alloc::sync::Arc;
+#[cfg(feature = "clock")]
+use chrono::Local;
+use chrono::{DateTime, Duration, FixedOffset, TimeZone, Utc};
+use core::{
+  fmt::{self, Debug, Formatter},
+  sync::atomic::{AtomicI64, Ordering},
+};
+
+/// The source of the current time for everything time dependent like the game clock,
+/// time limits and the solver time budget.
+pub trait Clock: Send + Sync {
+  fn now(&self) -> DateTime<Utc>;
+  /// The current time in the local time zone. Only used for display purposes.
+  fn now_local(&self) -> DateTime<FixedOffset> {
+    self.now().fixed_offset()
+  }
+}
+
+/// The system clock. Requires the `clock` feature.
+#[cfg(feature = "clock")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SystemClock;
+
+#[cfg(feature = "clock")]
+impl Clock for SystemClock {
+  fn now(&self) -> DateTime<Utc> {
+    Utc::now()
+  }
+  fn now_local(&self) -> DateTime<FixedOffset> {
+    Local::now().fixed_offset()
+  }
+}
+
+/// A clock that only moves when told to. Useful for tests and deterministic replays.
+/// Starts at the Unix epoch by default.
+#[derive(Debug, Default)]
+pub struct ManualClock {
+  now_ms: AtomicI64,
+}
+
+impl ManualClock {
+  #[must_use]
+  pub fn new(now: DateTime<Utc>) -> Self {
+    Self {
+      now_ms: AtomicI64::new(now.timestamp_millis()),
+    }
+  }
+
+  pub fn set(&self, now: DateTime<Utc>) {
+    self.now_ms.store(now.timestamp_millis(), Ordering::Relaxed);
+  }
+
+  /// Moves the clock forward. Only millisecond precision is kept.
+  pub fn advance(&self, duration: Duration) {
+    self
+      .now_ms
+      .fetch_add(duration.num_milliseconds(), Ordering::Relaxed);
+  }
+}
+
+impl Clock for ManualClock {
+  fn now(&self) -> DateTime<Utc> {
+    Utc
+      .timestamp_millis_opt(self.now_ms.load(Ordering::Relaxed))
+      .single()
+      .unwrap_or_default()
+  }
+}
+
+/// A `Clock` shared between games.
+///
+/// All shared clocks compare equal, so the types holding one can still implement `PartialEq`.
+/// The default is the `SystemClock`, or a `ManualClock` at the Unix epoch without the `clock` feature.
+#[derive(Clone)]
+pub struct SharedClock(Arc<dyn Clock>);
+
+impl SharedClock {
+  pub fn new(clock: impl Clock + 'static) -> Self {
+    Self(Arc::new(clock))
+  }
+
+  #[must_use]
+  pub fn now(&self) -> DateTime<Utc> {
+    self.0.now()
+  }
+
+  #[must_use]
+  pub fn now_local(&self) -> DateTime<FixedOffset> {
+    self.0.now_local()
+  }
+}
+
+/// Keeps access to the clock, for example to advance a `ManualClock` in tests.
+impl<C: Clock + 'static> From<Arc<C>> for SharedClock {
+  fn from(clock: Arc<C>) -> Self {
+    Self(clock)
+  }
+}
+
+impl Default for SharedClock {
+  #[cfg(feature = "clock")]
+  fn default() -> Self {
+    Self::new(SystemClock)
+  }
+  #[cfg(not(feature = "clock"))]
+  fn default() -> Self {
+    Self::new(ManualClock::default())
+  }
+}
+
+impl Debug for SharedClock {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "SharedClock({})", self.now())
+  }
+}
+
+impl PartialEq for SharedClock {
+  fn eq(&self, _other: &Self) -> bool {
+    true // The clock is not part of the game state
+  }
+}
+impl Eq for SharedClock {}
@@ -0,0 +1,197 @@
+use super::{
+  Direction, GameField, GameState, Grid2D, Playable, Pos, Seed, Size2D, Solver, SolverConfig,
+  TileGrid,
+};
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
+use rand::prelude::*;
+use sha2::{Digest, Sha512};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DifficultyConfig {
+  /// Amount of random games played on the board.
+  pub playouts: usize,
+  /// Used to estimate the best possible score.
+  pub solver: SolverConfig,
+}
+
+impl DifficultyConfig {
+  pub const DEFAULT_PLAYOUTS: usize = 64;
+}
+
+impl Default for DifficultyConfig {
+  fn default() -> Self {
+    Self {
+      playouts: Self::DEFAULT_PLAYOUTS,
+      solver: SolverConfig::default(),
+    }
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DifficultyRating {
+  Easy,
+  Medium,
+  Hard,
+}
+
+impl DifficultyRating {
+  /// Random play reaching at least this permille of the best score is easy.
+  pub const EASY_PERMILLE: usize = 500;
+  /// Random play reaching at least this permille of the best score is medium.
+  pub const MEDIUM_PERMILLE: usize = 250;
+
+  #[must_use]
+  pub const fn from_playout_permille(playout_permille: usize) -> Self {
+    if playout_permille >= Self::EASY_PERMILLE {
+      Self::Easy
+    } else if playout_permille >= Self::MEDIUM_PERMILLE {
+      Self::Medium
+    } else {
+      Self::Hard
+    }
+  }
+}
+
+/// How often each tile was consumed across all playouts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DifficultyMap {
+  size: Size2D,
+  playouts: usize,
+  consumed: Box<[usize]>,
+}
+
+impl DifficultyMap {
+  fn new(size: Size2D, playouts: usize) -> Self {
+    Self {
+      size,
+      playouts,
+      consumed: alloc::vec![0; size.cell_count()].into_boxed_slice(),
+    }
+  }
+
+  /// In how many playouts the tile at `index` was consumed.
+  #[must_use]
+  pub fn consumed(&self, index: usize) -> Option<usize> {
+    self.consumed.get(index).copied()
+  }
+
+  #[must_use]
+  pub fn consumed_at(&self, pos: Pos) -> Option<usize> {
+    self.consumed(self.pos_to_index(pos)?)
+  }
+
+  /// In how many permille of the playouts the tile at `index` was consumed.
+  /// Rarely consumed tiles are hard to get.
+  #[must_use]
+  pub fn consumed_permille(&self, index: usize) -> Option<usize> {
+    Some(self.consumed(index)? * 1000 / self.playouts.max(1))
+  }
+
+  #[must_use]
+  pub const fn playouts(&self) -> usize {
+    self.playouts
+  }
+}
+
+impl Grid2D for DifficultyMap {
+  fn dimensions(&self) -> Size2D {
+    self.size
+  }
+}
+
+/// Difficulty statistics of a board.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Difficulty {
+  pub playouts: usize,
+  pub average_playout_score: usize,
+  pub best_playout_score: usize,
+  pub worst_playout_score: usize,
+  /// Average amount of possible moves per turn in permille over all playouts.
+  pub average_branching_permille: usize,
+  /// The amount of possible moves at the start.
+  pub initial_branching: usize,
+  /// The best score the solver found.
+  pub solver_score: usize,
+  pub solver_proven_optimal: bool,
+  /// `solver_score` as permille of the `tile_count()`.
+  pub solver_permille: usize,
+  /// `average_playout_score` as permille of `solver_score`.
+  pub playout_permille: usize,
+  pub rating: DifficultyRating,
+  pub map: DifficultyMap,
+}
+
+impl Difficulty {
+  /// Estimates the difficulty of a seed.
+  /// The playouts are derived from the seed so the result is reproducible.
+  #[must_use]
+  pub fn estimate_seed(seed: &Seed, config: &DifficultyConfig) -> Self {
+    let mut hasher = Sha512::new();
+    hasher.update(String::from(seed));
+    hasher.update("#difficulty");
+    let hash = hasher.finalize();
+    let mut used_hash = [0; 16];
+    used_hash.copy_from_slice(&hash[0..16]);
+    let mut rng = rand_pcg::Pcg64Mcg::from_seed(used_hash);
+    Self::estimate(&GameField::from_seed(seed), config, &mut rng)
+  }
+
+  #[must_use]
+  pub fn estimate(game_field: &GameField, config: &DifficultyConfig, rng: &mut impl Rng) -> Self {
+    let initial_state = GameState::new(Arc::new(game_field.clone()));
+    let possible_moves = |game_state: &GameState| -> Vec<Direction> {
+      game_state
+        .rule_set()
+        .directions()
+        .filter(|&dir| game_state.check_move(dir).is_ok())
+        .collect()
+    };
+
+    let mut map = DifficultyMap::new(game_field.dimensions(), config.playouts);
+    let mut scores = Vec::with_capacity(config.playouts);
+    let mut branching_sum = 0;
+    let mut turns = 0;
+    for _ in 0..config.playouts {
+      let mut game_state = initial_state.clone();
+      loop {
+        let dirs = possible_moves(&game_state);
+        let Some(&dir) = dirs.choose(rng) else {
+          break;
+        };
+        let Ok(consumed) = game_state.move_(dir) else {
+          break; // Can't happen since only possible moves are chosen
+        };
+        branching_sum += dirs.len();
+        turns += 1;
+        for index in consumed {
+          map.consumed[index] += 1;
+        }
+      }
+      scores.push(game_state.score());
+    }
+
+    let initial_score = initial_state.score();
+    let average_playout_score = if scores.is_empty() {
+      initial_score
+    } else {
+      scores.iter().sum::<usize>() / scores.len()
+    };
+    let solution = Solver::new(config.solver.clone()).solve(&initial_state);
+    let playout_permille = average_playout_score * 1000 / solution.score.max(1);
+
+    Self {
+      playouts: config.playouts,
+      average_playout_score,
+      best_playout_score: scores.iter().copied().max().unwrap_or(initial_score),
+      worst_playout_score: scores.iter().copied().min().unwrap_or(initial_score),
+      average_branching_permille: branching_sum * 1000 / turns.max(1),
+      initial_branching: possible_moves(&initial_state).len(),
+      solver_score: solution.score,
+      solver_proven_optimal: solution.proven_optimal,
+      solver_permille: solution.score * 1000 / game_field.tile_count(),
+      playout_permille,
+      rating: DifficultyRating::from_playout_permille(playout_permille),
+      map,
+    }
+  }
+}
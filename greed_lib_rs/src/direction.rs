@@ -58,6 +58,45 @@ impl Direction {
   pub fn reverse(self) -> Self {
     !self
   }
+
+  /// Numpad digits and compass names in the same order as `ALL_DIRECTIONS_CW`.
+  const NUMPAD_DIGITS: [char; 8] = ['8', '9', '6', '3', '2', '1', '4', '7'];
+  const COMPASS_NAMES: [&'static str; 8] = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+
+  pub(super) fn cw_index(self) -> Option<usize> {
+    let dir = self.reduce();
+    Self::ALL_DIRECTIONS_CW
+      .into_iter()
+      .position(|valid_dir| valid_dir == dir)
+  }
+
+  /// The numpad key pointing in this direction. `None` for invalid directions.
+  #[must_use]
+  pub fn numpad_digit(self) -> Option<char> {
+    Some(Self::NUMPAD_DIGITS[self.cw_index()?])
+  }
+
+  /// Parses a numpad key like `'8'` (UP) or `'3'` (DOWN | RIGHT).
+  #[must_use]
+  pub fn from_numpad_digit(digit: char) -> Option<Self> {
+    let index = Self::NUMPAD_DIGITS.iter().position(|&key| key == digit)?;
+    Some(Self::ALL_DIRECTIONS_CW[index])
+  }
+
+  /// The compass name like `"N"` (UP) or `"SE"` (DOWN | RIGHT). `None` for invalid directions.
+  #[must_use]
+  pub fn compass_name(self) -> Option<&'static str> {
+    Some(Self::COMPASS_NAMES[self.cw_index()?])
+  }
+
+  /// Parses a compass name like `"N"` or `"se"` ignoring the case.
+  #[must_use]
+  pub fn from_compass_name(name: &str) -> Option<Self> {
+    let index = Self::COMPASS_NAMES
+      .iter()
+      .position(|compass_name| compass_name.eq_ignore_ascii_case(name))?;
+    Some(Self::ALL_DIRECTIONS_CW[index])
+  }
 }
 
 impl<T> Mul<T> for Direction
@@ -3,24 +3,35 @@ use core::fmt::{self, Debug, Formatter};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+/// Serialized as the amount, walls use the value of `Tile::Wall`.
 #[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(try_from = "u8")]
 #[serde(into = "u8")]
 pub struct FakeTile {
-  amount: u8,
+  value: u8,
 }
 
 impl FakeTile {
-  pub const EMTPY: Self = Self { amount: 0 };
+  pub const EMTPY: Self = Self { value: 0 };
+  pub const WALL: Self = Self::from_unchecked(Tile::Wall);
 
+  /// Walls have an amount of 0.
   pub const fn amount(self) -> u8 {
-    self.amount
+    if self.value == Self::WALL.value {
+      0
+    } else {
+      self.value
+    }
+  }
+  /// Empty tiles and walls can't be moved onto.
+  pub fn is_blocking(self) -> bool {
+    self == Self::EMTPY || self == Self::WALL
   }
   pub const fn from_unchecked(tile: Tile) -> Self {
-    Self { amount: tile as u8 }
+    Self { value: tile as u8 }
   }
   pub const fn from_unchecked_u8(tile: u8) -> Self {
-    Self { amount: tile }
+    Self { value: tile }
   }
 }
 
@@ -32,7 +43,7 @@ pub enum FakeTileConversionError {
 
 impl From<FakeTile> for u8 {
   fn from(value: FakeTile) -> Self {
-    value.amount
+    value.value
   }
 }
 
@@ -40,8 +51,11 @@ impl TryFrom<u8> for FakeTile {
   type Error = AmountConversionError;
 
   fn try_from(value: u8) -> Result<Self, Self::Error> {
+    if value == Self::WALL.value {
+      return Ok(Self::WALL);
+    }
     Ok(Self {
-      amount: Amount::try_from(value)?.amount(),
+      value: Amount::try_from(value)?.amount(),
     })
   }
 }
@@ -50,15 +64,18 @@ impl TryFrom<Tile> for FakeTile {
   type Error = FakeTileConversionError;
 
   fn try_from(value: Tile) -> Result<Self, Self::Error> {
+    if value == Tile::Wall {
+      return Ok(Self::WALL);
+    }
     let amount = value.amount().ok_or(FakeTileConversionError::PlayerTile)?;
-    Ok(Self { amount })
+    Ok(Self { value: amount })
   }
 }
 
 impl From<FakeTile> for Tile {
   fn from(fake_tile: FakeTile) -> Self {
-    Self::try_from(fake_tile.amount)
-      .expect("fake_tile.ammount is at always <= 9 therefore Tile::try_from can never fail")
+    Self::try_from(fake_tile.value)
+      .expect("fake_tile.value is always <= 9 or a wall therefore Tile::try_from can never fail")
   }
 }
 
@@ -1,11 +1,12 @@
 use super::{
-  FakeTile, FakeTileConversionError, GameFieldParserError, GameState, Grid2D, Pos, Seed, Size2D,
-  Tile, TileChooser, TileGet, TileGrid,
+  FakeTile, FakeTileConversionError, GameFieldParserError, GameState, Grid2D, Playable, Pos,
+  RuleSet, Seed, Size2D, Tile, TileGet, TileGrid, Topology,
 };
 use alloc::{boxed::Box, fmt, format, string::String, vec::Vec};
+use bitvec::prelude as bv;
 use core::fmt::{Debug, Display, Formatter};
 use rand::prelude::*;
-use serde::{de, Deserialize, Deserializer, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use sha2::{Digest, Sha512};
 
 /// This immutable structure represents the initial state of a game of greed.
@@ -17,12 +18,34 @@ pub struct GameField {
   size: Size2D,
   /// initial player_pos
   player_pos: Pos,
+  /// Stored in `GameMeta` and `Seed` instead of the game field json.
+  #[serde(skip)]
+  topology: Topology,
+  /// Stored in `GameMeta` instead of the game field json.
+  #[serde(skip)]
+  rule_set: RuleSet,
+  /// The playable area of non-rectangular boards. `None` if every cell is playable.
+  /// Off-board cells always contain a WALL tile. Serialized as the indices of the off-board cells.
+  #[serde(
+    rename = "off_board",
+    serialize_with = "serialize_off_board",
+    skip_serializing_if = "Option::is_none"
+  )]
+  playable: Option<bv::BitBox>,
+}
+
+#[allow(clippy::ref_option)] // Signature required by serde
+fn serialize_off_board<S>(playable: &Option<bv::BitBox>, serializer: S) -> Result<S::Ok, S::Error>
+where
+  S: Serializer,
+{
+  serializer.collect_seq(playable.iter().flat_map(|playable| playable.iter_zeros()))
 }
 
 impl GameField {
   /// Not exposed because it is counter intuitive
   pub(super) fn new_from_game_state(game_state: &GameState) -> Self {
-    let vec = (0..game_state.tile_count())
+    let vec = (0..game_state.cell_count())
       .map(|index| game_state.get_fake_unchecked(index))
       .collect();
 
@@ -30,24 +53,68 @@ impl GameField {
       vec,
       size: game_state.dimensions(),
       player_pos: game_state.player_pos(),
+      topology: game_state.topology(),
+      rule_set: game_state.rule_set(),
+      playable: game_state.game_field().playable.clone(),
     }
   }
 
-  fn new_random(tile_chooser: &mut TileChooser<impl Rng>, size: Size2D) -> Self {
-    let mut vec: Box<_> = (0..size.tile_count())
-      .map(|_| tile_chooser.choose())
-      .collect();
-    #[allow(clippy::cast_possible_wrap)] // Can nerver wrap since Sice2D contains limited sizes.
-    let player_pos = Pos {
-      x: tile_chooser.rng.gen_range(0..size.x_size) as isize,
-      y: tile_chooser.rng.gen_range(0..size.y_size) as isize,
-    };
+  /// Returns `None` if `vec` doesn't match `size` or the player isn't on a valid EMPTY tile.
+  pub(super) fn from_parts(vec: Box<[FakeTile]>, size: Size2D, player_pos: Pos) -> Option<Self> {
+    let player_index = size.pos_to_index(player_pos)?;
+    if vec.len() != size.cell_count() || vec[player_index] != FakeTile::EMTPY {
+      return None;
+    }
+    Some(Self {
+      vec,
+      size,
+      player_pos,
+      topology: Topology::Bounded,
+      rule_set: RuleSet::EIGHT_WAY,
+      playable: None,
+    })
+  }
+
+  /// The character used for off-board cells of non-rectangular boards.
+  pub const OFF_BOARD_CHAR: char = '.';
+
+  /// Restricts the board to the cells set in `playable`. Off-board cells are turned into walls.
+  /// Returns `None` if `playable` doesn't match the size or the player would be off-board.
+  pub(super) fn with_playable(mut self, playable: bv::BitBox) -> Option<Self> {
+    if playable.len() != self.vec.len() || !playable[self.pos_to_index_unchecked(self.player_pos)] {
+      return None;
+    }
+    for index in playable.iter_zeros() {
+      self.vec[index] = FakeTile::WALL;
+    }
+    self.playable = Some(playable).filter(|playable| !playable.all());
+    Some(self)
+  }
+
+  /// Places the players of a multiplayer game. Every start becomes an EMPTY tile
+  /// and the first start becomes the `player_pos`.
+  /// `starts` must not be empty and only contain playable positions.
+  pub(super) fn with_players(mut self, starts: &[Pos]) -> Self {
+    for &start in starts {
+      let index = self.pos_to_index_unchecked(start);
+      self.vec[index] = FakeTile::EMTPY;
+    }
+    self.player_pos = starts[0];
+    self
+  }
+
+  /// A generated board. The tile at `player_pos` is replaced with an empty tile.
+  /// `vec` must match `size` and `player_pos` must be valid.
+  pub(super) fn new_generated(mut vec: Box<[FakeTile]>, size: Size2D, player_pos: Pos) -> Self {
     vec[size.pos_to_index_unchecked(player_pos)] = FakeTile::EMTPY;
 
     Self {
       vec,
       size,
       player_pos,
+      topology: Topology::Bounded,
+      rule_set: RuleSet::EIGHT_WAY,
+      playable: None,
     }
   }
 
@@ -60,8 +127,29 @@ impl GameField {
       .expect("Can never fail since we actually statically know the size");
     // init the random gen with the first 16 bytes of the hash
     let mut rng = rand_pcg::Pcg64Mcg::from_seed(used_hash);
-    let mut tile_chooser = TileChooser::new(&mut rng, seed.tile_probabilities());
-    Self::new_random(&mut tile_chooser, seed.size())
+    seed
+      .generator()
+      .generator()
+      .generate(seed.size(), seed.tile_probabilities(), &mut rng)
+      .with_topology(seed.topology())
+  }
+
+  #[must_use]
+  pub const fn with_topology(mut self, topology: Topology) -> Self {
+    self.topology = topology;
+    self
+  }
+
+  #[must_use]
+  pub const fn with_rule_set(mut self, rule_set: RuleSet) -> Self {
+    self.rule_set = rule_set;
+    self
+  }
+
+  /// The rules moves on this game field are checked with.
+  #[must_use]
+  pub const fn rule_set(&self) -> RuleSet {
+    self.rule_set
   }
 }
 
@@ -76,8 +164,29 @@ impl Grid2D for GameField {
     self.size
   }
 
+  fn topology(&self) -> Topology {
+    self.topology
+  }
+
+  fn cell_count(&self) -> usize {
+    self.vec.len()
+  }
+
   fn tile_count(&self) -> usize {
     self.vec.len()
+      - self
+        .playable
+        .as_ref()
+        .map_or(0, |playable| playable.count_zeros())
+  }
+
+  fn is_playable(&self, pos: Pos) -> bool {
+    self.pos_to_index(pos).is_some_and(|index| {
+      self
+        .playable
+        .as_ref()
+        .is_none_or(|playable| playable[index])
+    })
   }
 
   // The following functions are implemented as wrappers to make sure they aren't generated again
@@ -129,37 +238,32 @@ impl TryFrom<&str> for GameField {
     if value.is_empty() {
       return Err(GameFieldParserError::NoTrailingNewLine); // Mabe add a better error
     }
-    let default_size = Size2D::DEFAULT_SIZE;
-    let mut vec = Vec::with_capacity(default_size.tile_count());
-
-    let mut x_size = None;
-    let mut x_pos = 0;
-    let mut y_pos = 0;
+    // Tiles of every line with `None` for off-board cells
+    let mut lines: Vec<Vec<Option<FakeTile>>> = Vec::new();
+    let mut line = Vec::with_capacity(Size2D::DEFAULT_SIZE.x_size);
     let mut player_pos = None;
     for c in value.chars() {
       match c {
         '\n' => {
-          if x_pos == 0 {
+          if line.is_empty() {
             return Err(GameFieldParserError::EmptyLine);
           }
-          if let Some(x_size) = x_size {
-            if x_pos != x_size {
-              return Err(GameFieldParserError::NotRectangular);
-            }
-          } else {
-            x_size = Some(x_pos);
+          if lines.first().is_some_and(|first| first.len() != line.len()) {
+            return Err(GameFieldParserError::NotRectangular);
           }
-          x_pos = 0;
-          y_pos += 1;
+          lines.push(core::mem::take(&mut line));
         },
+        Self::OFF_BOARD_CHAR => line.push(None),
         c => {
           // This technically doesn't need to be always calculated, but its po
           let pos = {
             Pos::new(
-              x_pos
+              line
+                .len()
                 .try_into()
                 .map_err(|_| GameFieldParserError::InvalidSize)?,
-              y_pos
+              lines
+                .len()
                 .try_into()
                 .map_err(|_| GameFieldParserError::InvalidSize)?,
             )
@@ -170,39 +274,48 @@ impl TryFrom<&str> for GameField {
           })?;
 
           match FakeTile::try_from(tile) {
-            Ok(tile) => vec.push(tile),
+            Ok(tile) => line.push(Some(tile)),
             Err(FakeTileConversionError::PlayerTile) => {
               if let Some(first) = player_pos {
                 return Err(GameFieldParserError::AmbiguousPlayer { first, second: pos });
               }
               player_pos = Some(pos);
-              vec.push(FakeTile::EMTPY);
+              line.push(Some(FakeTile::EMTPY));
             },
           }
-
-          x_pos += 1;
         },
       }
     }
 
-    if x_pos != 0 {
+    if !line.is_empty() {
       return Err(GameFieldParserError::NoTrailingNewLine);
     }
 
-    let size = Size2D::new(
-      x_size.expect("since x_size is set on newlines and y_pos != 0"),
-      y_pos,
-    )
-    .map_err(|_| GameFieldParserError::InvalidSize)?;
-
-    assert!(vec.len() == size.tile_count());
-    let vec = vec.into_boxed_slice();
+    let x_size = lines
+      .first()
+      .expect("since lines are pushed on newlines and the value isn't empty")
+      .len();
+    let size = Size2D::new(x_size, lines.len()).map_err(|_| GameFieldParserError::InvalidSize)?;
+
+    let mut vec = Vec::with_capacity(size.cell_count());
+    let mut playable = bv::BitVec::with_capacity(size.cell_count());
+    for line in lines {
+      for tile in line {
+        playable.push(tile.is_some());
+        vec.push(tile.unwrap_or(FakeTile::WALL));
+      }
+    }
 
     let game_field = Self {
-      vec,
+      vec: vec.into_boxed_slice(),
       size,
       player_pos: player_pos.ok_or(GameFieldParserError::PlayerNotFound)?,
-    };
+      topology: Topology::Bounded,
+      rule_set: RuleSet::EIGHT_WAY,
+      playable: None,
+    }
+    .with_playable(playable.into_boxed_bitslice())
+    .expect("since the size matches and the player is never off-board");
     Ok(game_field)
   }
 }
@@ -270,14 +383,17 @@ impl<'de> Deserialize<'de> for GameField {
       vec: Box<[FakeTile]>,
       size: Size2D,
       player_pos: Pos,
+      #[serde(default)]
+      off_board: Vec<usize>,
     }
     let InnerGameField {
       vec,
       size,
       player_pos,
+      off_board,
     } = InnerGameField::deserialize(deserializer)?;
     // validate that the size matches the vec len
-    if vec.len() != size.tile_count() {
+    if vec.len() != size.cell_count() {
       return Err(de::Error::custom(format!(
         "GameField length {} incompatible with size {}",
         vec.len(),
@@ -301,10 +417,25 @@ impl<'de> Deserialize<'de> for GameField {
         Tile::from(tile),
       )));
     }
-    Ok(Self {
+    let mut playable = bv::bitbox![1; vec.len()];
+    for index in off_board {
+      if index >= vec.len() {
+        return Err(de::Error::custom(format!(
+          "Off-board index {index} is not valid. Expected 0..{}",
+          vec.len()
+        )));
+      }
+      playable.set(index, false);
+    }
+    Self {
       vec,
       size,
       player_pos,
-    })
+      topology: Topology::Bounded,
+      rule_set: RuleSet::EIGHT_WAY,
+      playable: None,
+    }
+    .with_playable(playable)
+    .ok_or_else(|| de::Error::custom(format!("Player pos {player_pos} is off-board")))
   }
 }
@@ -1,6 +1,6 @@
 use super::{
-  Amount, Direction, FakeTile, GameField, Grid2D, Playable, PlayableError, Pos, Size2D, Tile,
-  TileGet, TileGrid,
+  Amount, CheckedMove, Direction, FakeTile, GameField, Grid2D, MoveValidationError, Playable,
+  PlayableError, Pos, Size2D, Tile, TileGet, TileGrid, Topology, TravelDistance,
 };
 use alloc::{format, string::String, sync::Arc, vec::Vec};
 use bitvec::prelude as bv;
@@ -36,6 +36,8 @@ pub struct GameState {
   mask: bv::BitVec,
   player_pos: Pos,
   moves: Vec<(Direction, Amount)>,
+  /// Undone moves. The last element is the next move to redo.
+  redo_moves: Vec<(Direction, Amount)>,
   game_field: Arc<GameField>,
 }
 
@@ -46,15 +48,15 @@ impl GameState {
   ) -> Self {
     let player_pos = game_field.player_pos();
     let player_index = game_field.pos_to_index(player_pos).unwrap();
-    let tile_count = game_field.tile_count();
+    let cell_count = game_field.cell_count();
 
-    let mut mask = bv::BitVec::with_capacity(tile_count);
-    mask.resize(tile_count, true);
+    let mut mask = bv::BitVec::with_capacity(cell_count);
+    mask.resize(cell_count, true);
     // https://docs.rs/bitvec/latest/bitvec/vec/struct.BitVec.html#writing-into-a-bit-vector
     mask.set(player_index, false);
 
     // apply already empty tiles from game_field to mask - sometimes avoids 2 deep lookups
-    for it in 0..tile_count {
+    for it in 0..cell_count {
       let last_tile = game_field.vec[it];
       if last_tile == FakeTile::EMTPY {
         mask.set(it, false);
@@ -65,6 +67,7 @@ impl GameState {
       mask,
       player_pos,
       moves,
+      redo_moves: Vec::new(),
       game_field,
     }
   }
@@ -90,12 +93,12 @@ impl GameState {
     let player_pos = last_game_field.player_pos();
     let player_index = last_game_field.pos_to_index_unchecked(player_pos);
 
-    let tile_count = initial_size.tile_count();
-    let mut mask = bv::BitVec::with_capacity(tile_count);
-    mask.resize(tile_count, true);
+    let cell_count = initial_size.cell_count();
+    let mut mask = bv::BitVec::with_capacity(cell_count);
+    mask.resize(cell_count, true);
     mask.set(player_index, false);
 
-    for it in 0..tile_count {
+    for it in 0..cell_count {
       let initial_tile = initial_game_field.vec[it];
       let last_tile = last_game_field.vec[it];
       if last_tile == FakeTile::EMTPY {
@@ -113,15 +116,59 @@ impl GameState {
       mask,
       player_pos,
       moves,
+      redo_moves: Vec::new(),
       game_field: initial_game_field,
     })
   }
 
+  /// Replays `moves` on `game_field` up to the first move that is invalid or consumes a different
+  /// amount than recorded. The recorded moves are kept as they are,
+  /// so `validate_moves` reports the first move that doesn't match.
+  pub(super) fn rebuild_from_moves(
+    game_field: Arc<GameField>,
+    moves: Vec<(Direction, Amount)>,
+  ) -> Self {
+    let mut game_state = Self::new(game_field);
+    for &(dir, recorded) in &moves {
+      match game_state.check_move(dir) {
+        Ok(indices) if indices.len() == usize::from(recorded.amount()) => {
+          game_state.commit_move_unchecked(dir, indices);
+        },
+        _ => break,
+      }
+    }
+    game_state.moves = moves;
+    game_state
+  }
+
   #[must_use]
   pub fn moves(&self) -> &[(Direction, Amount)] {
     &self.moves
   }
 
+  /// The moves that can be redone. The last element is the next move `redo_move` executes.
+  #[must_use]
+  pub fn redo_moves(&self) -> &[(Direction, Amount)] {
+    &self.redo_moves
+  }
+
+  /// The redo moves are only checked once they are actually redone.
+  pub(super) fn with_redo_moves(mut self, redo_moves: Vec<(Direction, Amount)>) -> Self {
+    self.redo_moves = redo_moves;
+    self
+  }
+
+  /// Moves the player without consuming anything. Used to switch turns in multiplayer games.
+  /// `pos` must already be consumed.
+  pub(super) fn set_player_pos(&mut self, pos: Pos) {
+    debug_assert!(!self.mask[self.pos_to_index_unchecked(pos)]);
+    self.player_pos = pos;
+  }
+
+  pub(super) fn mask(&self) -> &bv::BitVec {
+    &self.mask
+  }
+
   pub(super) fn get_fake_unchecked(&self, index: usize) -> FakeTile {
     #[allow(clippy::bool_comparison)]
     // self.mask[index] == false is purposefully used over !self.mask[index] i
@@ -132,14 +179,112 @@ impl GameState {
     }
   }
 
-  fn move_set(&mut self, pos: Pos, dir: Direction, amount: u8, mask: bool) {
+  fn move_set(&mut self, pos: Pos, vector: Pos, amount: u8, mask: bool) {
     let mut pos = pos;
 
     for _ in 0..amount {
       let index = self.pos_to_index_unchecked(pos);
       self.mask.set(index, mask);
-      pos += dir;
+      let Some(next) = self.offset(pos, vector) else {
+        break;
+      };
+      pos = next;
+    }
+  }
+
+  /// The indices a move from `pos` in `dir` would consume on the current mask.
+  /// See `check_move`, which calls this with the player pos.
+  pub(super) fn move_path(&self, pos: Pos, dir: Direction) -> Result<Vec<usize>, PlayableError> {
+    if !dir.is_valid() {
+      return Err(PlayableError::InvalidDirection);
+    }
+    let rule_set = self.rule_set();
+    let vector = rule_set
+      .vector(dir)
+      .ok_or(PlayableError::DirectionNotAllowed)?;
+    let travel_distance = rule_set.travel_distance();
+
+    let mut current_pos = pos;
+    let mut values = [0; TravelDistance::MAX_DISTANCE];
+    let mut moves = Vec::with_capacity(TravelDistance::MAX_DISTANCE);
+    // collect positions and check for collision -> BadMove
+    for step in 0..TravelDistance::MAX_DISTANCE {
+      current_pos = self
+        .offset(current_pos, vector)
+        .ok_or(PlayableError::BadMove)?;
+      let index = self.pos_to_index_unchecked(current_pos);
+      let tile = self.get_fake_unchecked(index);
+      if tile.is_blocking() {
+        return Err(PlayableError::BadMove);
+      }
+      values[step] = tile.amount();
+      moves.push(index);
+      if travel_distance.ends_at(&values[..=step]) {
+        return Ok(moves);
+      }
+    }
+    Err(PlayableError::BadMove)
+  }
+
+  /// Commits the movements returned by `check_move`.
+  fn commit_move_unchecked(&mut self, dir: Direction, moves: Vec<usize>) -> Vec<usize> {
+    let mut iter = moves.iter().rev();
+    // If check_move is successful the returned vec contains at least one element
+    let &player_index = iter.next().unwrap();
+    self.player_pos = self.index_to_pos_unchecked(player_index);
+
+    self.mask.set(player_index, false);
+
+    for &index in iter {
+      self.mask.set(index, false);
+    }
+
+    // update the moves array
+    #[allow(clippy::cast_possible_truncation)]
+    // moves.len() can never be bigger than 9 since it originates from a loop over ammount.
+    let executed_move = (dir, Amount::new_unchecked(moves.len() as u8));
+    self.moves.push(executed_move);
+
+    // Redoing keeps the rest of the redo stack, any other move diverges from it
+    if self.redo_moves.last() == Some(&executed_move) {
+      let _ = self.redo_moves.pop();
+    } else {
+      self.redo_moves.clear();
+    }
+    moves
+  }
+
+  /// Replays the moves array on a fresh state of the same game field.
+  /// Checks that every move is possible, that every recorded amount matches the amount actually consumed
+  /// and that the replayed state equals this state.
+  /// # Errors
+  /// * The first move that can't be replayed or has a wrong amount. `move_number` is the index into `moves`.
+  /// * The first position where the replayed state differs from this state.
+  pub fn validate_moves(&self) -> Result<(), MoveValidationError> {
+    let mut replay = Self::new(self.game_field.clone());
+    for (move_number, &(dir, recorded)) in self.moves.iter().enumerate() {
+      let consumed = replay
+        .move_(dir)
+        .map_err(|_| MoveValidationError::InvalidMove { move_number })?;
+      #[allow(clippy::cast_possible_truncation)] // A move never consumes more than 9 tiles
+      let consumed = Amount::new_unchecked(consumed.len() as u8);
+      if consumed != recorded {
+        return Err(MoveValidationError::AmountMismatch {
+          move_number,
+          recorded,
+          consumed,
+        });
+      }
     }
+
+    let mismatch = (0..self.cell_count())
+      .find(|&index| replay.get_unchecked(index) != self.get_unchecked(index));
+    if let Some(index) = mismatch {
+      return Err(MoveValidationError::EndStateMismatch {
+        pos: self.index_to_pos_unchecked(index),
+      });
+    }
+    Ok(())
   }
 
   /// Creates a new `game_field` from the current `game_state`.
@@ -193,106 +338,90 @@ impl TileGet<Pos> for GameState {
 
 impl Playable for GameState {
   fn check_move(&self, dir: Direction) -> Result<Vec<usize>, PlayableError> {
-    let mut current_pos = self.player_pos + dir;
-    // check if direction was valid - is the same as calling dir.valid() obviously
-    if current_pos == self.player_pos {
-      return Err(PlayableError::InvalidDirection);
-    }
-
-    let starting_index = self
-      .pos_to_index(current_pos)
-      .ok_or(PlayableError::BadMove)?;
-    // The first tile the player moves to
-    // Tells us how many tiles to move
-    let starting_tile = self.get_fake_unchecked(starting_index);
-    if starting_tile == FakeTile::EMTPY {
-      return Err(PlayableError::BadMove);
-    }
-    let move_amount = starting_tile.amount();
-
-    let mut moves = Vec::with_capacity(move_amount.into());
-    // push the tile that gave us the amount
-    moves.push(starting_index);
-    // collect positions and check for collision -> BadMove
-    for _ in 1..move_amount {
-      current_pos += dir;
-      let index = self
-        .pos_to_index(current_pos)
-        .ok_or(PlayableError::BadMove)?;
-      let tile = self.get_fake_unchecked(index);
-      if tile == FakeTile::EMTPY {
-        return Err(PlayableError::BadMove);
-      }
-      moves.push(index);
-    }
-
-    // return movements
-    Ok(moves)
+    self.move_path(self.player_pos, dir)
   }
 
-  /// TODO: give the user a more efficient way to execute a move checked by `check_move`?
-  /// Maybe introduce a `CheckedMove` type to do that safely?
-  ///
   /// For the return see `check_move` function.
   fn move_(&mut self, dir: Direction) -> Result<Vec<usize>, PlayableError> {
-    // commit movements
     let moves = self.check_move(dir)?;
+    Ok(self.commit_move_unchecked(dir, moves))
+  }
 
-    let mut iter = moves.iter().rev();
-    // If check_move is successful the returned vec contains at least one element
-    let &player_index = iter.next().unwrap();
-    self.player_pos = self.index_to_pos_unchecked(player_index);
-
-    self.mask.set(player_index, false);
+  fn checked_move(&self, dir: Direction) -> Result<CheckedMove, PlayableError> {
+    Ok(CheckedMove {
+      dir,
+      indices: self.check_move(dir)?,
+      origin: Some((self.player_pos, self.game_field.clone())),
+    })
+  }
 
-    for &index in iter {
-      self.mask.set(index, false);
+  fn commit_move(&mut self, checked_move: CheckedMove) -> Result<Vec<usize>, PlayableError> {
+    // The tiles of the game field never change so the move is still valid
+    // if the player didn't move and none of the tiles were consumed since it was checked.
+    let is_stale = checked_move
+      .origin
+      .as_ref()
+      .is_none_or(|(player_pos, game_field)| {
+        !Arc::ptr_eq(game_field, &self.game_field) || *player_pos != self.player_pos
+      })
+      || checked_move.indices.iter().any(|&index| !self.mask[index]);
+    if is_stale {
+      return Err(PlayableError::StaleCheckedMove);
     }
-
-    // update the moves array
-    #[allow(clippy::cast_possible_truncation)]
-    // moves.len() can never be bigger than 9 since it originates from a loop over ammount.
-    self
-      .moves
-      .push((dir, Amount::new_unchecked(moves.len() as u8)));
-    Ok(moves)
+    Ok(self.commit_move_unchecked(checked_move.dir, checked_move.indices))
   }
 
   fn undo_move(&mut self) -> Result<(), PlayableError> {
     let last_move = self.moves.last().ok_or(PlayableError::BadMove)?;
     let &(dir, amount) = last_move;
-    // dir.valid()?; // always valid we control the moves
-    let dir = !dir; // invert the direction bit flag magic
-
-    let end_pos = self.player_pos + dir * amount.amount();
-
-    if !self.is_valid_pos(end_pos) {
-      // Restore the moves array back to a "valid" state
-      return Err(PlayableError::UndoInvalidMove);
+    // The moves were allowed by the rule set when they were executed
+    let vector = -self
+      .rule_set()
+      .vector(dir)
+      .ok_or(PlayableError::UndoInvalidMove)?;
+
+    let mut end_pos = self.player_pos;
+    for _ in 0..amount.amount() {
+      end_pos = self
+        .offset(end_pos, vector)
+        .ok_or(PlayableError::UndoInvalidMove)?;
     }
     // 1..amount because we don't want to uncheck the player
     for already_moved_tiles in 0..amount.amount() {
       // pos_to_index_unchecked is safe her because the initial player position is considered safe
-      //  and we verified that end_pos is safe. Therefore all positions in between must also be safe.
+      //  and we verified that every step to end_pos is safe.
 
       let index = self.pos_to_index_unchecked(self.player_pos);
 
       if self.player_pos != end_pos && self.mask[index] {
-        self.move_set(self.player_pos, !dir, already_moved_tiles, false); // undo the partial undo in order to revert the breakage of the mask.
+        self.move_set(self.player_pos, -vector, already_moved_tiles, false); // undo the partial undo in order to revert the breakage of the mask.
 
         return Err(PlayableError::UndoInvalidMove);
       }
 
       self.mask.set(index, true);
-      self.player_pos += dir;
+      self.player_pos = self.offset(self.player_pos, vector).unwrap_or(end_pos);
     }
     // move the player pos without setting the mask to true
 
-    let _ = self.moves.pop();
+    if let Some(last_move) = self.moves.pop() {
+      self.redo_moves.push(last_move);
+    }
 
     Ok(())
   }
 
+  fn redo_move(&mut self) -> Result<Vec<usize>, PlayableError> {
+    let &(dir, amount) = self.redo_moves.last().ok_or(PlayableError::BadMove)?;
+    let moves = self
+      .check_move(dir)
+      .map_err(|_| PlayableError::RedoInvalidMove)?;
+    if moves.len() != usize::from(amount.amount()) {
+      return Err(PlayableError::RedoInvalidMove);
+    }
+    Ok(self.commit_move_unchecked(dir, moves))
+  }
+
   fn game_field(&self) -> &GameField {
     &self.game_field
   }
@@ -307,10 +436,22 @@ impl Grid2D for GameState {
     self.game_field.dimensions()
   }
 
-  fn tile_count(&self) -> usize {
+  fn topology(&self) -> Topology {
+    self.game_field.topology()
+  }
+
+  fn cell_count(&self) -> usize {
     self.mask.len()
   }
 
+  fn tile_count(&self) -> usize {
+    self.game_field.tile_count()
+  }
+
+  fn is_playable(&self, pos: Pos) -> bool {
+    self.game_field.is_playable(pos)
+  }
+
   // The following functions are implemented as wrappers to make sure they aren't generated again
   fn is_valid_pos(&self, pos: Pos) -> bool {
     self.game_field.is_valid_pos(pos)
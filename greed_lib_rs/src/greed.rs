@@ -1,32 +1,59 @@
 use super::{
-  Amount, Direction, GameField, GameState, GreedParserError, Grid2D, MoveValidationError, Playable,
-  PlayableError, Pos, ReproductionError, Seed, Size2D, Tile, TileGet, TileGrid,
+  hints, save_migration, Amount, BinaryFormatError, Challenge, CheckedMove, Direction, GameField,
+  GameState, GreedParserError, Grid2D, Hint, HintConfig, HumanScore, MoveList, MoveValidationError,
+  Playable, PlayableError, Pos, Reachability, ReproductionError, RuleSet, Seed, SharedClock,
+  Size2D, Tile, TileGet, TileGrid, Topology, BINARY_MAGIC, CURRENT_FILE_VERSION,
 };
 use alloc::{format, string::String, sync::Arc, vec::Vec};
-use chrono::{DateTime, Duration, Local, TimeZone, Utc};
+use chrono::{DateTime, Duration, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
-use serde_with::skip_serializing_none;
+use serde_with::{serde_as, skip_serializing_none};
 
+#[serde_as]
 #[skip_serializing_none]
 #[derive(Clone, Default, Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub struct GameMeta {
   pub file_version: Option<u64>,
   pub greed_version: Option<u64>,
   pub seed: Option<Seed>,
+  /// The challenge the game belongs to. The `seed` must be the seed of the challenge.
+  pub challenge: Option<Challenge>,
+  /// Missing means the topology of the `seed` or `Topology::Bounded` without a seed.
+  pub topology: Option<Topology>,
+  /// Missing means `RuleSet::EIGHT_WAY`.
+  pub rule_set: Option<RuleSet>,
   pub name: Option<String>,
   pub utc_started_ms: Option<i64>,
   pub utc_finished_ms: Option<i64>,
+  /// Time spent playing. Never counts paused time and never exceeds `time_limit_ms`.
   #[serde(default)]
   pub time_spent_ms: i64,
+  /// Time spent paused.
+  #[serde(default)]
+  pub idle_ms: i64,
+  /// If the game was paused when it was saved. Paused games stay paused when loaded.
+  #[serde(default)]
+  pub paused: bool,
+  /// The game ends once `time_spent_ms` reaches the limit. Missing means no limit.
+  pub time_limit_ms: Option<i64>,
+  /// Also accepts `MoveNotation` like `"N3 NE1"` when loading.
+  #[serde_as(deserialize_as = "Option<MoveList>")]
   pub moves: Option<Vec<(Direction, Amount)>>,
+  /// Undone moves that can still be redone. The last element is redone first.
+  #[serde_as(deserialize_as = "Option<MoveList>")]
+  pub redo_moves: Option<Vec<(Direction, Amount)>>,
   pub score: Option<usize>,
   /// A score based on time spent, moves (counting undos) and a few more factors.
   /// This value can't be verified and is designed to increase human engagement.
-  /// TODO: Maybe also store average_move_time.
+  /// It is ignored on load and recomputed from the other fields. See `HumanScore`.
   pub human_score: Option<usize>,
   pub undos: Option<usize>,
+  /// The `time_spent_ms` at which each move or undo was made in the order they were made.
+  pub move_timestamps_ms: Option<Vec<i64>>,
   pub initial_game_field: Option<GameField>,
   pub last_game_field: Option<GameField>,
+  /// HMAC-SHA256 of the `replay_digest` as hex. See `GameMeta::sign`.
+  pub signature: Option<String>,
 }
 
 impl GameMeta {
@@ -39,19 +66,30 @@ impl GameMeta {
       .finished_instant
       .map(|instant| instant.timestamp_millis());
     Self {
-      file_version: Some(1),
+      file_version: Some(CURRENT_FILE_VERSION),
       greed_version: Some(1),
       seed: greed.seed.clone(),
+      challenge: greed.challenge.clone(),
+      topology: Some(greed.topology()),
+      rule_set: Some(greed.rule_set()),
       name: Some(greed.name.clone()),
       utc_started_ms,
       utc_finished_ms,
       time_spent_ms: greed.time_spent().num_milliseconds(),
+      idle_ms: greed.idle_time().num_milliseconds(),
+      paused: greed.is_paused(),
+      time_limit_ms: greed
+        .time_limit
+        .map(|time_limit| time_limit.num_milliseconds()),
       moves: Some(greed.game_state.moves().to_vec()),
+      redo_moves: Some(greed.game_state.redo_moves().to_vec()),
       score: Some(greed.score()),
       human_score: Some(greed.human_score()),
       undos: Some(greed.undos),
+      move_timestamps_ms: Some(greed.move_timestamps_ms.clone()),
       initial_game_field: Some(greed.game_field().clone()),
       last_game_field: Some(greed.game_state.to_game_field()),
+      signature: None,
     }
   }
 }
@@ -60,29 +98,53 @@ impl GameMeta {
 pub struct Greed {
   /// None if the game is custom game and the seed is unkown.
   seed: Option<Seed>,
+  challenge: Option<Challenge>,
   /// We will just pick a name if we don't get one.
   name: String,
   /// None if the game was loaded from a string with no starting time.
   started_instant: Option<DateTime<Utc>>,
   finished_instant: Option<DateTime<Utc>>,
-  started_session: DateTime<Utc>,
+  /// Start of the running session. `None` while paused.
+  started_session: Option<DateTime<Utc>>,
+  /// Start of the current pause. `None` while running.
+  paused_since: Option<DateTime<Utc>>,
+  /// Time spent in previous sessions.
   time_spent: Duration,
+  /// Time spent paused excluding the current pause.
+  idle_time: Duration,
+  time_limit: Option<Duration>,
+  clock: SharedClock,
   undos: usize,
+  move_timestamps_ms: Vec<i64>,
   game_state: GameState,
 }
 
 impl Greed {
-  pub(super) fn new_from_builder(name: String, seed: Seed) -> Self {
-    let game_field = Arc::from(GameField::from_seed(&seed));
+  pub(super) fn new_from_builder(
+    name: String,
+    seed: Seed,
+    challenge: Option<Challenge>,
+    rule_set: RuleSet,
+    time_limit: Option<Duration>,
+    clock: SharedClock,
+  ) -> Self {
+    let game_field = Arc::from(GameField::from_seed(&seed).with_rule_set(rule_set));
+    let now = clock.now();
 
     Greed {
       seed: Some(seed),
+      challenge,
       name,
-      started_instant: Some(Utc::now()),
+      started_instant: Some(now),
       finished_instant: None,
-      started_session: Utc::now(),
+      started_session: Some(now),
+      paused_since: None,
       time_spent: Duration::seconds(0),
+      idle_time: Duration::seconds(0),
+      time_limit,
+      clock,
       undos: 0,
+      move_timestamps_ms: Vec::new(),
       game_state: GameState::new(game_field),
     }
   }
@@ -97,20 +159,102 @@ impl Greed {
     self.seed.as_ref()
   }
 
-  // pub fn load_from_reader() {}
-  // pub fn save_to_writer() {}
+  /// The challenge this game belongs to. See `GreedBuilder::challenge`.
+  #[must_use]
+  pub const fn challenge(&self) -> Option<&Challenge> {
+    self.challenge.as_ref()
+  }
+
+  /// Like `load_from_string` but also accepts the binary format of `save_to_bytes`.
+  /// # Errors
+  /// * If `bytes` start with `BINARY_MAGIC` but aren't a valid binary save.
+  /// * If `bytes` aren't valid UTF-8.
+  /// * See `load_from_string`
+  pub fn load_from_bytes(bytes: &[u8]) -> Result<Greed, GreedParserError> {
+    Self::load_from_bytes_with_clock(bytes, SharedClock::default())
+  }
+
+  /// Like `load_from_bytes` but measures the time with `clock`.
+  /// # Errors
+  /// See `load_from_bytes`
+  pub fn load_from_bytes_with_clock(
+    bytes: &[u8],
+    clock: SharedClock,
+  ) -> Result<Greed, GreedParserError> {
+    if bytes.starts_with(&BINARY_MAGIC[..1]) {
+      return Self::load_from_game_meta_with_clock(GameMeta::from_bytes(bytes)?, clock);
+    }
+    let str = core::str::from_utf8(bytes).map_err(|_| BinaryFormatError::InvalidUtf8)?;
+    Self::load_from_string_with_clock(str, clock)
+  }
+
+  /// Saves the game in the compact binary format. See `GameMeta::to_bytes`.
+  /// # Errors
+  /// If a move or redo move has an invalid direction.
+  /// Loaded redo moves are only checked once they are redone.
+  pub fn save_to_bytes(&self) -> Result<Vec<u8>, BinaryFormatError> {
+    GameMeta::new(self).to_bytes()
+  }
+
+  /// Like `load_from_bytes` but reads the save from `reader` first.
+  /// # Errors
+  /// * If reading fails.
+  /// * See `load_from_bytes`
+  #[cfg(feature = "std")]
+  pub fn load_from_reader(reader: impl std::io::Read) -> Result<Greed, GreedParserError> {
+    Self::load_from_reader_with_clock(reader, SharedClock::default())
+  }
+
+  /// Like `load_from_reader` but measures the time with `clock`.
+  /// # Errors
+  /// See `load_from_reader`
+  #[cfg(feature = "std")]
+  pub fn load_from_reader_with_clock(
+    mut reader: impl std::io::Read,
+    clock: SharedClock,
+  ) -> Result<Greed, GreedParserError> {
+    // Saves are small, keeping the text is a lot cheaper than building a json `Value` of it
+    let mut bytes = Vec::new();
+    reader
+      .read_to_end(&mut bytes)
+      .map_err(|err| GreedParserError::Io { source: err.into() })?;
+    Self::load_from_bytes_with_clock(&bytes, clock)
+  }
+
+  /// Like `save_to_string` but streams the json into `writer`.
+  /// # Errors
+  /// If writing fails.
+  #[cfg(feature = "std")]
+  pub fn save_to_writer(&self, writer: impl std::io::Write) -> Result<(), GreedParserError> {
+    let meta = GameMeta::new(self);
+    // Serialize is never implemented manually and no Maps are used so only io errors are possible
+    serde_json::to_writer(writer, &meta).map_err(|err| GreedParserError::Io {
+      source: std::io::Error::from(err).into(),
+    })
+  }
 
   /// Accepts either `GameMeta` as json or one GameField-String and creates a Greed instance from it.
+  /// Json written by older versions is migrated to the current layout first.
   /// # Errors
-  /// If if `str` doesn't contain a valid serialized Game
+  /// * If if `str` doesn't contain a valid serialized Game
+  /// * If the `file_version` is newer than `CURRENT_FILE_VERSION`
   pub fn load_from_string(str: &str) -> Result<Greed, GreedParserError> {
+    Self::load_from_string_with_clock(str, SharedClock::default())
+  }
+
+  /// Like `load_from_string` but measures the time with `clock`.
+  /// # Errors
+  /// See `load_from_string`
+  pub fn load_from_string_with_clock(
+    str: &str,
+    clock: SharedClock,
+  ) -> Result<Greed, GreedParserError> {
     // load the meta data if available
 
     #[allow(clippy::iter_nth_zero)]
     let first_char = str.chars().nth(0).ok_or(GreedParserError::EmptyString)?;
     let game_meta = if first_char == '{' {
-      serde_json::from_str::<GameMeta>(str)
-        .map_err(|err| GreedParserError::InvalidMetaDataFromat { source: err.into() })?
+      save_migration::migrate(str)?
     } else {
       // Create default game_meta and set initial_field
       GameMeta {
@@ -118,28 +262,62 @@ impl Greed {
         ..Default::default()
       }
     };
+    Self::load_from_game_meta_with_clock(game_meta, clock)
+  }
+
+  /// Creates a Greed instance from already deserialized meta data.
+  /// # Errors
+  /// If `game_meta` doesn't describe a valid Game
+  pub fn load_from_game_meta(game_meta: GameMeta) -> Result<Greed, GreedParserError> {
+    Self::load_from_game_meta_with_clock(game_meta, SharedClock::default())
+  }
 
+  /// Like `load_from_game_meta` but measures the time with `clock`.
+  /// # Errors
+  /// See `load_from_game_meta`
+  pub fn load_from_game_meta_with_clock(
+    game_meta: GameMeta,
+    clock: SharedClock,
+  ) -> Result<Greed, GreedParserError> {
+    if let Some(challenge) = &game_meta.challenge {
+      if game_meta.seed.as_ref() != Some(&challenge.seed()) {
+        return Err(GreedParserError::ChallengeSeedMismatch {
+          challenge: challenge.clone(),
+        });
+      }
+    }
+    // The game field json doesn't contain the topology
+    let topology = game_meta
+      .topology
+      .or_else(|| game_meta.seed.as_ref().map(Seed::topology))
+      .unwrap_or_default();
+    let rule_set = game_meta.rule_set.unwrap_or_default();
     // assemble the game_field
     let game_field = Arc::from(
       game_meta
         .initial_game_field
         .or_else(|| game_meta.last_game_field.clone())
         .or_else(|| game_meta.seed.as_ref().map(GameField::from_seed))
-        .ok_or(GreedParserError::MissingGameFieldInformation)?,
+        .ok_or(GreedParserError::MissingGameFieldInformation)?
+        .with_topology(topology)
+        .with_rule_set(rule_set),
     );
 
     let moves = game_meta.moves.unwrap_or_default();
 
     let game_state = if let Some(last_game_field) = game_meta.last_game_field {
-      GameState::try_rebuild_from_game_field_diff(game_field, &last_game_field, moves)?
+      GameState::try_rebuild_from_game_field_diff(
+        game_field,
+        &last_game_field
+          .with_topology(topology)
+          .with_rule_set(rule_set),
+        moves,
+      )?
     } else {
       // reconstruct the game_state by applying all moves to the inital_game_state
-      let mut game_state = GameState::new(game_field);
-      for move_ in moves {
-        game_state.move_(move_.0)?;
-      }
-      game_state
+      GameState::rebuild_from_moves(game_field, moves)
     };
+    let game_state = game_state.with_redo_moves(game_meta.redo_moves.unwrap_or_default());
 
     // get the game name
     let name = game_meta
@@ -148,12 +326,15 @@ impl Greed {
       .unwrap_or_else(|| {
         format!(
           "{}",
-          Local::now().format("Custom Game from %d/%b/%Y %H:%M:%S")
+          clock
+            .now_local()
+            .format("Custom Game from %d/%b/%Y %H:%M:%S")
         )
       });
 
     Ok(Self {
       seed: game_meta.seed,
+      challenge: game_meta.challenge,
       name,
       started_instant: game_meta
         .utc_started_ms
@@ -162,9 +343,14 @@ impl Greed {
         .utc_finished_ms
         .and_then(|utc_finished_ms| Utc.timestamp_millis_opt(utc_finished_ms).single()),
 
-      started_session: Utc::now(),
+      started_session: (!game_meta.paused).then(|| clock.now()),
+      paused_since: game_meta.paused.then(|| clock.now()),
       time_spent: Duration::milliseconds(game_meta.time_spent_ms),
+      idle_time: Duration::milliseconds(game_meta.idle_ms),
+      time_limit: game_meta.time_limit_ms.map(Duration::milliseconds),
+      clock,
       undos: game_meta.undos.unwrap_or(0),
+      move_timestamps_ms: game_meta.move_timestamps_ms.unwrap_or_default(),
       game_state,
     })
   }
@@ -186,14 +372,99 @@ impl Greed {
     &self.game_state
   }
 
+  /// Time since the game was started, loaded or resumed. Zero while paused.
   #[must_use]
   pub fn session_time(&self) -> Duration {
-    Utc::now() - self.started_session
+    self
+      .started_session
+      .map_or_else(Duration::zero, |started_session| {
+        self.clock.now() - started_session
+      })
   }
 
+  /// Time spent playing. Paused time isn't counted and it never exceeds the `time_limit`.
   #[must_use]
   pub fn time_spent(&self) -> Duration {
-    self.time_spent + self.session_time()
+    let time_spent = self.time_spent + self.session_time();
+    self
+      .time_limit
+      .map_or(time_spent, |time_limit| time_spent.min(time_limit))
+  }
+
+  /// Time spent paused.
+  #[must_use]
+  pub fn idle_time(&self) -> Duration {
+    self.idle_time
+      + self
+        .paused_since
+        .map_or_else(Duration::zero, |paused_since| {
+          self.clock.now() - paused_since
+        })
+  }
+
+  #[must_use]
+  pub const fn is_paused(&self) -> bool {
+    self.paused_since.is_some()
+  }
+
+  /// Stops the clock. Does nothing if the game is already paused.
+  pub fn pause(&mut self) {
+    if let Some(started_session) = self.started_session.take() {
+      let now = self.clock.now();
+      self.time_spent += now - started_session;
+      self.paused_since = Some(now);
+    }
+  }
+
+  /// Restarts the clock. Does nothing if the game isn't paused.
+  /// Moving while paused resumes the game as well.
+  pub fn resume(&mut self) {
+    if let Some(paused_since) = self.paused_since.take() {
+      let now = self.clock.now();
+      self.idle_time += now - paused_since;
+      self.started_session = Some(now);
+    }
+  }
+
+  /// The clock the time is measured with.
+  #[must_use]
+  pub const fn clock(&self) -> &SharedClock {
+    &self.clock
+  }
+
+  #[must_use]
+  pub const fn time_limit(&self) -> Option<Duration> {
+    self.time_limit
+  }
+
+  /// Turns the game into a countdown. `None` removes the limit.
+  pub fn set_time_limit(&mut self, time_limit: Option<Duration>) {
+    self.time_limit = time_limit;
+  }
+
+  /// Time left until the `time_limit` runs out. `None` without a limit.
+  #[must_use]
+  pub fn remaining_time(&self) -> Option<Duration> {
+    self
+      .time_limit
+      .map(|time_limit| time_limit - self.time_spent())
+  }
+
+  /// If the `time_limit` ran out. No moves can be made anymore.
+  #[must_use]
+  pub fn is_time_up(&self) -> bool {
+    self
+      .remaining_time()
+      .is_some_and(|remaining| remaining <= Duration::zero())
+  }
+
+  /// Resumes the game for a move or fails if the time ran out.
+  fn start_move(&mut self) -> Result<(), PlayableError> {
+    if self.is_time_up() {
+      return Err(PlayableError::TimeUp);
+    }
+    self.resume();
+    Ok(())
   }
 
   #[must_use]
@@ -206,25 +477,91 @@ impl Greed {
     self.undos
   }
 
-  /// Validates the moves array
-  #[allow(clippy::missing_panics_doc, clippy::missing_errors_doc)] // Remove when actually implemented
-  pub fn validate_moves() -> Result<(), MoveValidationError> {
-    todo!()
+  /// Validates the moves array by replaying it on the initial game field.
+  /// Every recorded amount must match the consumed amount and the replayed end state must match
+  /// the current game state (which was loaded from `last_game_field` if one was present).
+  /// # Errors
+  /// See `GameState::validate_moves`
+  pub fn validate_moves(&self) -> Result<(), MoveValidationError> {
+    self.game_state.validate_moves()
   }
 
   /// Validates if the seed reproduces the saved game state and checks that all moves are valid.
-  /// Also uses a difficulty map if available.
-  #[allow(clippy::missing_panics_doc, clippy::missing_errors_doc)]
-  // Remove when actually implemented
-  pub fn validate_reproducibility() -> Result<(), ReproductionError> {
-    todo!()
+  /// The difficulty of a seed can be estimated separately with `Difficulty::estimate_seed`.
+  /// # Errors
+  /// * If the game has no seed.
+  /// * If the game field generated from the seed differs in size, topology, player position or any tile.
+  /// * If the moves are invalid see `validate_moves`.
+  pub fn validate_reproducibility(&self) -> Result<(), ReproductionError> {
+    let seed = self.seed.as_ref().ok_or(ReproductionError::MissingSeed)?;
+    let seed_game_field = GameField::from_seed(seed);
+    let game_field = self.game_field();
+
+    let seed_size = seed_game_field.dimensions();
+    let game_field_size = game_field.dimensions();
+    if seed_size != game_field_size {
+      return Err(ReproductionError::WrongSize {
+        seed_size,
+        game_field_size,
+      });
+    }
+    let seed_topology = seed_game_field.topology();
+    let game_field_topology = game_field.topology();
+    if seed_topology != game_field_topology {
+      return Err(ReproductionError::WrongTopology {
+        seed_topology,
+        game_field_topology,
+      });
+    }
+
+    // Compares the player positions as well since `get_unchecked` returns `Tile::Player` for them
+    let mismatch = (0..seed_game_field.cell_count())
+      .find(|&index| seed_game_field.get_unchecked(index) != game_field.get_unchecked(index));
+    if let Some(index) = mismatch {
+      return Err(ReproductionError::WrongSeed {
+        pos: seed_game_field.index_to_pos_unchecked(index),
+        seed_tile: seed_game_field.get_unchecked(index),
+        game_field_tile: game_field.get_unchecked(index),
+      });
+    }
+
+    self.validate_moves()?;
+    Ok(())
+  }
+
+  #[must_use]
+  pub fn move_timestamps_ms(&self) -> &[i64] {
+    &self.move_timestamps_ms
+  }
+
+  /// Computes which tiles can still be consumed. See `Reachability`.
+  #[must_use]
+  pub fn reachability(&self) -> Reachability {
+    Reachability::new(&self.game_state)
+  }
+
+  /// Rates all possible moves. See `hints`.
+  #[must_use]
+  pub fn hints(&self, config: &HintConfig) -> Vec<Hint> {
+    hints(&self.game_state, config)
+  }
+
+  /// Computes the engagement score and all factors that went into it.
+  #[must_use]
+  pub fn human_score_breakdown(&self) -> HumanScore {
+    HumanScore::new(
+      self.score(),
+      self.tile_count(),
+      self.move_count(),
+      self.undos,
+      self.time_spent().num_milliseconds(),
+      &self.move_timestamps_ms,
+    )
   }
 
-  /// TODO: Returns 0 for now
   #[must_use]
-  #[allow(clippy::unused_self)] // Remove when actually implemented
   pub fn human_score(&self) -> usize {
-    0
+    self.human_score_breakdown().human_score
   }
 }
 
@@ -234,20 +571,63 @@ impl Playable for Greed {
   }
 
   fn check_move(&self, dir: Direction) -> Result<Vec<usize>, PlayableError> {
+    if self.is_time_up() {
+      return Err(PlayableError::TimeUp);
+    }
     self.game_state.check_move(dir)
   }
 
   fn move_(&mut self, dir: Direction) -> Result<Vec<usize>, PlayableError> {
-    self.game_state.move_(dir)
+    self.start_move()?;
+    let moves = self.game_state.move_(dir)?;
+    self
+      .move_timestamps_ms
+      .push(self.time_spent().num_milliseconds());
+    Ok(moves)
+  }
+
+  fn checked_move(&self, dir: Direction) -> Result<CheckedMove, PlayableError> {
+    if self.is_time_up() {
+      return Err(PlayableError::TimeUp);
+    }
+    self.game_state.checked_move(dir)
+  }
+
+  fn commit_move(&mut self, checked_move: CheckedMove) -> Result<Vec<usize>, PlayableError> {
+    self.start_move()?;
+    let moves = self.game_state.commit_move(checked_move)?;
+    self
+      .move_timestamps_ms
+      .push(self.time_spent().num_milliseconds());
+    Ok(moves)
   }
 
   fn undo_move(&mut self) -> Result<(), PlayableError> {
-    self.game_state.undo_move().map(|_| self.undos += 1)
+    self.start_move()?;
+    self.game_state.undo_move()?;
+    self.undos += 1;
+    self
+      .move_timestamps_ms
+      .push(self.time_spent().num_milliseconds());
+    Ok(())
+  }
+
+  fn redo_move(&mut self) -> Result<Vec<usize>, PlayableError> {
+    self.start_move()?;
+    let moves = self.game_state.redo_move()?;
+    self
+      .move_timestamps_ms
+      .push(self.time_spent().num_milliseconds());
+    Ok(moves)
   }
 
   fn move_count(&self) -> usize {
     self.game_state.moves().len()
   }
+
+  fn is_game_complete(&self) -> bool {
+    self.is_time_up() || self.game_state.is_game_complete()
+  }
 }
 
 impl<T> TileGet<T> for Greed
@@ -279,11 +659,23 @@ impl Grid2D for Greed {
     self.game_state.dimensions()
   }
 
+  fn topology(&self) -> Topology {
+    self.game_state.topology()
+  }
+
   // The following functions are implemented as wrappers to make sure they aren't generated again
+  fn cell_count(&self) -> usize {
+    self.game_state.cell_count()
+  }
+
   fn tile_count(&self) -> usize {
     self.game_state.tile_count()
   }
 
+  fn is_playable(&self, pos: Pos) -> bool {
+    self.game_state.is_playable(pos)
+  }
+
   fn is_valid_pos(&self, pos: Pos) -> bool {
     self.game_state.is_valid_pos(pos)
   }
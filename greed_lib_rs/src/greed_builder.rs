@@ -1,21 +1,34 @@
 use alloc::string::String;
+use chrono::Duration;
+use rand::Rng;
 
-use super::{Greed, Seed, Size2D, TileProbs};
+use super::{Challenge, Greed, RuleSet, Seed, SharedClock, Size2D, TileProbs};
 
 #[derive(Clone, PartialEq, Eq)]
 #[must_use]
 pub struct GreedBuilder {
   name: Option<String>,
+  rule_set: Option<RuleSet>,
+  time_limit: Option<Duration>,
+  clock: Option<SharedClock>,
 }
 
 impl GreedBuilder {
   pub const fn new() -> Self {
-    Self { name: None }
+    Self {
+      name: None,
+      rule_set: None,
+      time_limit: None,
+      clock: None,
+    }
   }
 
   pub fn size(self, size: Size2D) -> SizeProbGreedBuilder {
     SizeProbGreedBuilder {
       name: self.name,
+      rule_set: self.rule_set,
+      time_limit: self.time_limit,
+      clock: self.clock,
       size: Some(size),
       tile_probs: None,
     }
@@ -24,6 +37,9 @@ impl GreedBuilder {
   pub fn tile_probs(self, probs: TileProbs) -> SizeProbGreedBuilder {
     SizeProbGreedBuilder {
       name: self.name,
+      rule_set: self.rule_set,
+      time_limit: self.time_limit,
+      clock: self.clock,
       size: None,
       tile_probs: Some(probs),
     }
@@ -32,7 +48,24 @@ impl GreedBuilder {
   pub fn seed(self, seed: Seed) -> SeedGreedBuilder {
     SeedGreedBuilder {
       seed,
+      challenge: None,
       name: self.name,
+      rule_set: self.rule_set,
+      time_limit: self.time_limit,
+      clock: self.clock,
+    }
+  }
+
+  /// Plays the board of `challenge` and marks the game as part of it.
+  /// The name defaults to the id of the challenge.
+  pub fn challenge(self, challenge: Challenge) -> SeedGreedBuilder {
+    SeedGreedBuilder {
+      seed: challenge.seed(),
+      challenge: Some(challenge),
+      name: self.name,
+      rule_set: self.rule_set,
+      time_limit: self.time_limit,
+      clock: self.clock,
     }
   }
 
@@ -40,14 +73,39 @@ impl GreedBuilder {
     self.name = Some(name);
     self
   }
+  /// The rules of the game. Defaults to `RuleSet::EIGHT_WAY`.
+  pub fn rule_set(&mut self, rule_set: RuleSet) -> &mut Self {
+    self.rule_set = Some(rule_set);
+    self
+  }
+  /// Ends the game once `time_limit` was spent playing. See `Greed::set_time_limit`.
+  pub fn time_limit(&mut self, time_limit: Duration) -> &mut Self {
+    self.time_limit = Some(time_limit);
+    self
+  }
+  /// Measures the time of the game. Defaults to `SharedClock::default`.
+  pub fn clock(&mut self, clock: SharedClock) -> &mut Self {
+    self.clock = Some(clock);
+    self
+  }
+  /// Builds a game with a random seed from `rand::thread_rng`. Requires the `thread_rng` feature.
+  #[cfg(feature = "thread_rng")]
   #[must_use]
   pub fn build(self) -> Greed {
+    self.build_with_rng(&mut rand::thread_rng())
+  }
+  /// Builds a game with a random seed from `rng`.
+  #[must_use]
+  pub fn build_with_rng(self, rng: &mut impl Rng) -> Greed {
     SizeProbGreedBuilder {
       name: self.name,
+      rule_set: self.rule_set,
+      time_limit: self.time_limit,
+      clock: self.clock,
       size: None,
       tile_probs: None,
     }
-    .build()
+    .build_with_rng(rng)
   }
 }
 impl Default for GreedBuilder {
@@ -60,18 +118,47 @@ impl Default for GreedBuilder {
 #[must_use]
 pub struct SeedGreedBuilder {
   name: Option<String>,
+  rule_set: Option<RuleSet>,
+  time_limit: Option<Duration>,
+  clock: Option<SharedClock>,
   seed: Seed,
+  challenge: Option<Challenge>,
 }
 impl SeedGreedBuilder {
   pub fn name(&mut self, name: String) -> &mut Self {
     self.name = Some(name);
     self
   }
+  /// The rules of the game. Defaults to `RuleSet::EIGHT_WAY`.
+  pub fn rule_set(&mut self, rule_set: RuleSet) -> &mut Self {
+    self.rule_set = Some(rule_set);
+    self
+  }
+  /// Ends the game once `time_limit` was spent playing. See `Greed::set_time_limit`.
+  pub fn time_limit(&mut self, time_limit: Duration) -> &mut Self {
+    self.time_limit = Some(time_limit);
+    self
+  }
+  /// Measures the time of the game. Defaults to `SharedClock::default`.
+  pub fn clock(&mut self, clock: SharedClock) -> &mut Self {
+    self.clock = Some(clock);
+    self
+  }
   pub fn build(self) -> Greed {
-    let name = self
-      .name
-      .unwrap_or_else(|| String::from(self.seed.user_str()));
-    Greed::new_from_builder(name, self.seed)
+    let name = self.name.unwrap_or_else(|| {
+      self
+        .challenge
+        .as_ref()
+        .map_or_else(|| String::from(self.seed.user_str()), String::from)
+    });
+    Greed::new_from_builder(
+      name,
+      self.seed,
+      self.challenge,
+      self.rule_set.unwrap_or_default(),
+      self.time_limit,
+      self.clock.unwrap_or_default(),
+    )
   }
 }
 
@@ -79,17 +166,30 @@ impl SeedGreedBuilder {
 #[must_use]
 pub struct SizeProbGreedBuilder {
   name: Option<String>,
+  rule_set: Option<RuleSet>,
+  time_limit: Option<Duration>,
+  clock: Option<SharedClock>,
   size: Option<Size2D>,
   tile_probs: Option<TileProbs>,
 }
 impl SizeProbGreedBuilder {
+  /// Builds a game with a random seed from `rand::thread_rng`. Requires the `thread_rng` feature.
+  #[cfg(feature = "thread_rng")]
   pub fn build(self) -> Greed {
+    self.build_with_rng(&mut rand::thread_rng())
+  }
+  /// Builds a game with a random seed from `rng`.
+  pub fn build_with_rng(self, rng: &mut impl Rng) -> Greed {
     let size = self.size.unwrap_or(Size2D::DEFAULT_SIZE);
 
-    let seed = Seed::new_random(size, self.tile_probs);
+    let seed = Seed::new_random_with_rng(size, self.tile_probs, rng);
     SeedGreedBuilder {
       name: self.name,
+      rule_set: self.rule_set,
+      time_limit: self.time_limit,
+      clock: self.clock,
       seed,
+      challenge: None,
     }
     .build()
   }
@@ -105,4 +205,19 @@ impl SizeProbGreedBuilder {
     self.name = Some(name);
     self
   }
+  /// The rules of the game. Defaults to `RuleSet::EIGHT_WAY`.
+  pub fn rule_set(&mut self, rule_set: RuleSet) -> &mut Self {
+    self.rule_set = Some(rule_set);
+    self
+  }
+  /// Ends the game once `time_limit` was spent playing. See `Greed::set_time_limit`.
+  pub fn time_limit(&mut self, time_limit: Duration) -> &mut Self {
+    self.time_limit = Some(time_limit);
+    self
+  }
+  /// Measures the time of the game. Defaults to `SharedClock::default`.
+  pub fn clock(&mut self, clock: SharedClock) -> &mut Self {
+    self.clock = Some(clock);
+    self
+  }
 }
@@ -1,14 +1,25 @@
-use super::{GameStateRebuildFromDiffError, Pos};
+use super::{
+  Amount, Challenge, ChallengeConversionError, GameStateRebuildFromDiffError, Pos,
+  RuleSetConversionError, SeedConversionError, Size2D, Size2DConversionError, Tile, Topology,
+};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum PlayableError {
   #[error("Invalid direction")]
   InvalidDirection,
+  #[error("Direction not allowed by the rule set")]
+  DirectionNotAllowed,
   #[error("Bad move")]
   BadMove,
   #[error("Tried to undo an invalid move (probably originating from a corrupted save file)")]
   UndoInvalidMove,
+  #[error("Tried to redo an invalid move (probably originating from a corrupted save file)")]
+  RedoInvalidMove,
+  #[error("Checked move does not belong to the current game state")]
+  StaleCheckedMove,
+  #[error("The time limit ran out")]
+  TimeUp,
 }
 
 #[derive(Error, Debug, PartialEq, Eq)]
@@ -50,6 +61,81 @@ impl PartialEq for JsonErrorWrapper {
 }
 impl Eq for JsonErrorWrapper {}
 
+#[cfg(feature = "std")]
+#[derive(Error, Debug)]
+#[error(transparent)]
+pub struct IoErrorWrapper {
+  #[from]
+  source: std::io::Error,
+}
+
+#[cfg(feature = "std")]
+impl IoErrorWrapper {
+  #[must_use]
+  pub fn kind(&self) -> std::io::ErrorKind {
+    self.source.kind()
+  }
+}
+
+#[cfg(feature = "std")]
+impl PartialEq for IoErrorWrapper {
+  fn eq(&self, other: &Self) -> bool {
+    self.kind() == other.kind() // std::io::Error doesn't implement PartialEq, Eq
+  }
+}
+#[cfg(feature = "std")]
+impl Eq for IoErrorWrapper {}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum BinaryFormatError {
+  #[error("Neither a binary save nor valid UTF-8")]
+  InvalidUtf8,
+  #[error("Missing binary save header")]
+  MissingMagic,
+  #[error("Binary format version {found} is not supported (supported: {supported})")]
+  UnsupportedVersion { found: u8, supported: u8 },
+  #[error("Unexpected end of data")]
+  UnexpectedEnd,
+  #[error("{count} unexpected trailing bytes")]
+  TrailingBytes { count: usize },
+  #[error("Number does not fit")]
+  NumberOutOfRange,
+  #[error("Invalid tile value ({found})")]
+  InvalidTile { found: u8 },
+  #[error("Invalid move amount ({found})")]
+  InvalidAmount { found: u8 },
+  #[error("Invalid direction")]
+  InvalidDirection,
+  #[error("Invalid topology ({found})")]
+  InvalidTopology { found: u8 },
+  #[error("Invalid travel distance ({found})")]
+  InvalidTravelDistance { found: u8 },
+  #[error("Invalid rule set")]
+  InvalidRuleSet {
+    #[from]
+    source: RuleSetConversionError,
+  },
+  #[error("Invalid challenge")]
+  InvalidChallenge {
+    #[from]
+    source: ChallengeConversionError,
+  },
+  #[error("Invalid seed")]
+  InvalidSeed {
+    #[from]
+    source: SeedConversionError,
+  },
+  #[error("Invalid game field size")]
+  InvalidSize {
+    #[from]
+    source: Size2DConversionError,
+  },
+  #[error("Player is not on a valid empty tile")]
+  InvalidPlayerPos,
+  #[error("Only games with a seed or an initial game field can be stored")]
+  MissingInitialGameField,
+}
+
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum GreedParserError {
   #[error("Empty string")]
@@ -61,6 +147,15 @@ pub enum GreedParserError {
     #[from]
     source: JsonErrorWrapper,
   },
+  #[error("File version {found} is newer than the supported version {supported}")]
+  UnsupportedFileVersion { found: u64, supported: u64 },
+  #[error("The seed isn't the seed of the challenge {challenge}")]
+  ChallengeSeedMismatch { challenge: Challenge },
+  #[error("Invalid binary save")]
+  InvalidBinaryFormat {
+    #[from]
+    source: BinaryFormatError,
+  },
   #[error("Failed to parse game field")]
   GameFieldParserError {
     #[from]
@@ -76,12 +171,68 @@ pub enum GreedParserError {
     #[from]
     source: PlayableError,
   },
+  #[cfg(feature = "std")]
+  #[error("Failed to read or write the game")]
+  Io {
+    #[from]
+    source: IoErrorWrapper,
+  },
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum MultiplayerError {
+  #[error("Expected 2 to 26 players, found {found}")]
+  PlayerCount { found: usize },
+  #[error("Expected {expected} player names, found {found}")]
+  NameCount { expected: usize, found: usize },
+  #[error("Player {player} can't start at {pos}")]
+  InvalidStart { player: char, pos: Pos },
+  #[error("Player {player} not found on the game field")]
+  MissingPlayer { player: char },
+  #[error("Player {player} found twice on the game field ({first} and {second})")]
+  AmbiguousPlayer {
+    player: char,
+    first: Pos,
+    second: Pos,
+  },
+  #[error("Failed to parse game field")]
+  GameFieldParserError {
+    #[from]
+    source: GameFieldParserError,
+  },
+  #[error("Invalid meta data format")]
+  InvalidMetaDataFromat {
+    #[from]
+    source: JsonErrorWrapper,
+  },
+  #[error("Multiplayer file version {found} is newer than the supported version {supported}")]
+  UnsupportedFileVersion { found: u64, supported: u64 },
+  #[error("Move {move_number} is invalid")]
+  InvalidMove { move_number: usize },
 }
 
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum MoveValidationError {
   #[error("Move {move_number} is invalid")]
   InvalidMove { move_number: usize },
+  #[error("Move {move_number} recorded amount {recorded} but consumed {consumed} tiles")]
+  AmountMismatch {
+    move_number: usize,
+    recorded: Amount,
+    consumed: Amount,
+  },
+  #[error("Replayed game state does not match the saved game state at {pos}")]
+  EndStateMismatch { pos: Pos },
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum SignatureError {
+  #[error("Game is not signed")]
+  MissingSignature,
+  #[error("Signature is not valid hex")]
+  MalformedSignature,
+  #[error("Signature does not match (the game was edited or signed with another key)")]
+  SignatureMismatch,
 }
 
 #[derive(Error, Debug, PartialEq, Eq)]
@@ -91,6 +242,27 @@ pub enum ReproductionError {
     #[from]
     source: MoveValidationError,
   },
-  #[error("Seed does not match game field")]
-  WrongSeed,
+  #[error("Game has no seed")]
+  MissingSeed,
+  #[error("Seed size {seed_size} does not match game field size {game_field_size}")]
+  WrongSize {
+    seed_size: Size2D,
+    game_field_size: Size2D,
+  },
+  #[error("Seed topology {seed_topology} does not match game topology {game_field_topology}")]
+  WrongTopology {
+    seed_topology: Topology,
+    game_field_topology: Topology,
+  },
+  #[error(
+    "Seed does not match game field at {} - seed tile: {}; game field tile: {}",
+    pos,
+    seed_tile,
+    game_field_tile
+  )]
+  WrongSeed {
+    pos: Pos,
+    seed_tile: Tile,
+    game_field_tile: Tile,
+  },
 }
@@ -1,14 +1,37 @@
-use super::{Pos, Size2D};
+use super::{Direction, Pos, Size2D, Topology};
 
 pub trait Grid2D {
   /// For the default implementations to work the each
   /// value in the returned tuple must not exceed `isize::MAX`.
   fn dimensions(&self) -> Size2D;
-  /// Can also be interpreted as the maximum score
-  fn tile_count(&self) -> usize {
+  fn topology(&self) -> Topology {
+    Topology::Bounded
+  }
+  /// The position one step from `pos` in `dir` according to the `topology`.
+  /// `None` if the step leaves the playable area. `pos` must be valid.
+  fn neighbor(&self, pos: Pos, dir: Direction) -> Option<Pos> {
+    self.offset(pos, Pos::from(dir))
+  }
+  /// Like `neighbor` but moves by any `vector`.
+  fn offset(&self, pos: Pos, vector: Pos) -> Option<Pos> {
+    self
+      .topology()
+      .step(self.dimensions(), pos, vector)
+      .filter(|&pos| self.is_playable(pos))
+  }
+  /// All cells of the bounding rectangle including off-board cells. Every valid index is below this.
+  fn cell_count(&self) -> usize {
     let Size2D { x_size, y_size } = self.dimensions();
     x_size * y_size
   }
+  /// Can also be interpreted as the maximum score. Off-board cells are not counted.
+  fn tile_count(&self) -> usize {
+    self.cell_count()
+  }
+  /// Off-board cells of non-rectangular boards are valid positions but not playable.
+  fn is_playable(&self, pos: Pos) -> bool {
+    self.is_valid_pos(pos)
+  }
   #[allow(clippy::cast_possible_wrap)] // The sizes in Size2D are limited
   fn is_valid_pos(&self, pos: Pos) -> bool {
     let Size2D { x_size, y_size } = self.dimensions();
@@ -25,8 +48,8 @@ pub trait Grid2D {
   }
   /// Warning: This function can only catch if the index is out of bounds!
   fn valid_index(&self, index: usize) -> Option<usize> {
-    let tile_count = self.tile_count();
-    if index < tile_count {
+    let cell_count = self.cell_count();
+    if index < cell_count {
       Some(index)
     } else {
       None
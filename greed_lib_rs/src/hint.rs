@@ -0,0 +1,116 @@
+use super::{Amount, Direction, GameState, Grid2D, Playable, TileGet, TileGrid};
+use alloc::vec::Vec;
+use bitvec::prelude as bv;
+use core::cmp::Reverse;
+
+/// Controls how deep `hints` looks ahead and how it weights the heuristics.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HintConfig {
+  /// How many moves are looked at including the hinted move itself. `0` behaves like `1`.
+  pub depth: usize,
+  /// Weight per consumed tile.
+  pub consumed_weight: usize,
+  /// Weight per possible move after the lookahead.
+  pub mobility_weight: usize,
+  /// Weight per tile connected to the player after the lookahead.
+  pub reachable_weight: usize,
+}
+
+impl Default for HintConfig {
+  fn default() -> Self {
+    Self {
+      depth: 2,
+      consumed_weight: 4,
+      mobility_weight: 2,
+      reachable_weight: 1,
+    }
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Hint {
+  pub dir: Direction,
+  /// The amount of tiles the move itself consumes.
+  pub amount: Amount,
+  /// Higher is better. Only comparable between hints created with the same config.
+  pub rating: usize,
+}
+
+/// Rates every possible move and returns them ordered from best to worst.
+/// Moves with the same rating stay in `Direction::ALL_DIRECTIONS_CW` order.
+/// Only directions allowed by the rule set are rated.
+/// Returns an empty vec if the game is complete.
+#[must_use]
+pub fn hints(game_state: &GameState, config: &HintConfig) -> Vec<Hint> {
+  let mut working_state = game_state.clone();
+  let mut hints: Vec<Hint> = game_state
+    .rule_set()
+    .directions()
+    .filter_map(|dir| {
+      let consumed = working_state.move_(dir).ok()?.len();
+      let rating = consumed * config.consumed_weight
+        + lookahead(&mut working_state, config, config.depth.saturating_sub(1));
+      let undone = working_state.undo_move();
+      debug_assert!(undone.is_ok());
+      #[allow(clippy::cast_possible_truncation)] // A move never consumes more than 9 tiles
+      let amount = Amount::new_unchecked(consumed as u8);
+      Some(Hint {
+        dir,
+        amount,
+        rating,
+      })
+    })
+    .collect();
+  hints.sort_by_key(|hint| Reverse(hint.rating));
+  hints
+}
+
+/// The best rating reachable within `depth` moves.
+fn lookahead(game_state: &mut GameState, config: &HintConfig, depth: usize) -> usize {
+  let mut best = None;
+  if depth > 0 {
+    for dir in game_state.rule_set().directions() {
+      let Ok(consumed) = game_state.move_(dir) else {
+        continue;
+      };
+      let rating =
+        consumed.len() * config.consumed_weight + lookahead(game_state, config, depth - 1);
+      let undone = game_state.undo_move();
+      debug_assert!(undone.is_ok());
+      best = best.max(Some(rating));
+    }
+  }
+  // Also used if no move is possible anymore
+  best.unwrap_or_else(|| {
+    let mobility = game_state
+      .rule_set()
+      .directions()
+      .filter(|&dir| game_state.check_move(dir).is_ok())
+      .count();
+    mobility * config.mobility_weight + connected_tiles(game_state) * config.reachable_weight
+  })
+}
+
+/// Counts the non empty tiles that are connected to the player through non empty tiles.
+/// This is a cheap over approximation of the tiles that can still be consumed.
+fn connected_tiles(game_state: &GameState) -> usize {
+  let mut visited = bv::bitvec![0; game_state.cell_count()];
+  let mut stack = alloc::vec![game_state.player_pos()];
+  let mut count = 0;
+  let rule_set = game_state.rule_set();
+  while let Some(pos) = stack.pop() {
+    for vector in rule_set.vectors().iter().flatten() {
+      let Some(next) = game_state.offset(pos, *vector) else {
+        continue;
+      };
+      let index = game_state.pos_to_index_unchecked(next);
+      if visited[index] || game_state.get_unchecked(index).amount().unwrap_or(0) == 0 {
+        continue;
+      }
+      visited.set(index, true);
+      count += 1;
+      stack.push(next);
+    }
+  }
+  count
+}
@@ -0,0 +1,69 @@
+/// Breakdown of the engagement score of a game.
+///
+/// All factors are stored in permille so they can be displayed to explain the final `human_score`:
+/// `human_score = fill_permille * efficiency_permille * pace_permille / 1_000_000`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HumanScore {
+  /// How much of the board was cleared.
+  pub fill_permille: usize,
+  /// How many of the counted moves were not undos. Undoing is allowed but costs a bit.
+  pub efficiency_permille: usize,
+  /// Bonus or malus based on the average move time.
+  /// `1000` at `REFERENCE_MOVE_TIME_MS`, approaching `2000` for instant moves and `0` for very slow ones.
+  pub pace_permille: usize,
+  pub average_move_time_ms: i64,
+  pub human_score: usize,
+}
+
+impl HumanScore {
+  /// Average move time that neither rewards nor penalizes.
+  pub const REFERENCE_MOVE_TIME_MS: i64 = 2000;
+
+  /// Computes the score from the raw game statistics.
+  ///
+  /// `move_timestamps_ms` contains the `time_spent_ms` of every move and undo.
+  /// The last timestamp is used as the active play time so idling after the last move is not penalized.
+  /// If there are no timestamps `time_spent_ms` is used instead.
+  #[must_use]
+  pub fn new(
+    score: usize,
+    tile_count: usize,
+    move_count: usize,
+    undo_count: usize,
+    time_spent_ms: i64,
+    move_timestamps_ms: &[i64],
+  ) -> Self {
+    let total_move_count = move_count + undo_count;
+    let action_count = move_timestamps_ms.len().max(total_move_count);
+    if tile_count == 0 || action_count == 0 {
+      return Self {
+        fill_permille: 0,
+        efficiency_permille: 0,
+        pace_permille: 0,
+        average_move_time_ms: 0,
+        human_score: 0,
+      };
+    }
+
+    let fill_permille = score * 1000 / tile_count;
+    let efficiency_permille = move_count * 1000 / total_move_count.max(1);
+
+    let active_time_ms = move_timestamps_ms.last().copied().unwrap_or(time_spent_ms);
+    #[allow(clippy::cast_possible_wrap)] // Can never wrap since the board size is limited
+    let average_move_time_ms = active_time_ms.max(0) / action_count as i64;
+    #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+    // Always positive and <= 2000
+    let pace_permille = (2000 * Self::REFERENCE_MOVE_TIME_MS
+      / (Self::REFERENCE_MOVE_TIME_MS + average_move_time_ms)) as usize;
+
+    let human_score = fill_permille * efficiency_permille * pace_permille / 1_000_000;
+
+    Self {
+      fill_permille,
+      efficiency_permille,
+      pace_permille,
+      average_move_time_ms,
+      human_score,
+    }
+  }
+}
@@ -2,6 +2,8 @@
 #![allow(clippy::module_name_repetitions)]
 #![no_std]
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 mod greed;
 pub use greed::{GameMeta, Greed};
@@ -9,10 +11,16 @@ pub use greed::{GameMeta, Greed};
 pub mod greed_error;
 /// Make errors available internally
 use greed_error::{
-  GameFieldParserError, GreedParserError, MoveValidationError, PlayableError, ReproductionError,
-  TileParseError,
+  BinaryFormatError, GameFieldParserError, GreedParserError, MoveValidationError, MultiplayerError,
+  PlayableError, ReproductionError, SignatureError, TileParseError,
 };
 
+mod save_migration;
+pub use save_migration::CURRENT_FILE_VERSION;
+
+mod binary_save;
+pub use binary_save::BINARY_MAGIC;
+
 mod tile;
 pub use tile::Tile;
 
@@ -31,9 +39,21 @@ pub use game_state::{GameState, GameStateRebuildFromDiffError};
 mod playable;
 pub use playable::Playable;
 
+mod multiplayer;
+pub use multiplayer::{MultiplayerGame, MultiplayerMeta, MULTIPLAYER_FILE_VERSION};
+
+mod checked_move;
+pub use checked_move::CheckedMove;
+
 mod grid_2d;
 pub use grid_2d::Grid2D;
 
+mod topology;
+pub use topology::{Topology, TopologyConversionError};
+
+mod rule_set;
+pub use rule_set::{RuleSet, RuleSetConversionError, TravelDistance};
+
 mod tile_grid;
 pub use tile_grid::{
   ColIterator, RowIterator, StrideTileIterator, TileGet, TileGrid, TileIterator,
@@ -44,6 +64,15 @@ pub use tile_probs::{TileProbs, TileProbsConversionError};
 mod seed;
 pub use seed::{Seed, SeedConversionError, UserString, UserStringError};
 
+mod challenge;
+pub use challenge::{Challenge, ChallengeConversionError, ChallengePeriod};
+
+mod board_generator;
+pub use board_generator::{
+  BoardGenerator, BoardGeneratorId, BoardGeneratorIdConversionError, ClusteredGenerator,
+  HighClearanceGenerator, SymmetricGenerator, UniformGenerator,
+};
+
 mod tile_chooser;
 /// Internal
 use tile_chooser::TileChooser;
@@ -58,8 +87,36 @@ pub use amount::Amount;
 mod direction;
 pub use direction::Direction;
 
+mod move_notation;
+pub use move_notation::{parse_directions, parse_moves, MoveList, MoveNotation, MoveNotationError};
+
+mod replay_signature;
+
+mod share_code;
+pub use share_code::{ShareCode, ShareCodeError};
+
 mod greed_builder;
 pub use greed_builder::GreedBuilder;
 
+mod clock;
+#[cfg(feature = "clock")]
+pub use clock::SystemClock;
+pub use clock::{Clock, ManualClock, SharedClock};
+
+mod human_score;
+pub use human_score::HumanScore;
+
+mod solver;
+pub use solver::{Solution, Solver, SolverConfig};
+
+mod hint;
+pub use hint::{hints, Hint, HintConfig};
+
+mod reachability;
+pub use reachability::Reachability;
+
+mod difficulty;
+pub use difficulty::{Difficulty, DifficultyConfig, DifficultyMap, DifficultyRating};
+
 #[cfg(test)]
 mod test;
@@ -0,0 +1,191 @@
+use super::{Amount, Direction};
+use alloc::{
+  format,
+  string::{String, ToString},
+  vec::Vec,
+};
+use core::fmt;
+use serde::{
+  de::{self, value::SeqAccessDeserializer, SeqAccess, Visitor},
+  ser, Deserialize, Deserializer, Serializer,
+};
+use serde_with::{DeserializeAs, SerializeAs};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum MoveNotationError {
+  #[error("Invalid direction ({found})")]
+  InvalidDirection { found: String },
+  #[error("Invalid move ({found}) expected a direction followed by the amount")]
+  InvalidMove { found: String },
+}
+
+/// Textual notation of moves.
+///
+/// Directions are written as numpad digits (`8` is UP, `3` is DOWN | RIGHT)
+/// or compass names (`N` is UP, `SE` is DOWN | RIGHT).
+///
+/// Direction lists: `8963` or `N NE E SE`.
+/// Move lists append the amount to each direction: `83 91` or `N3 NE1`.
+///
+/// Parsing accepts both notations, mixed and separated by whitespace or commas.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum MoveNotation {
+  #[default]
+  Numpad,
+  Compass,
+}
+
+impl MoveNotation {
+  /// # Errors
+  /// If `dir` is invalid.
+  pub fn format_direction(self, dir: Direction) -> Result<String, MoveNotationError> {
+    let formatted = match self {
+      Self::Numpad => dir.numpad_digit().map(String::from),
+      Self::Compass => dir.compass_name().map(String::from),
+    };
+    formatted.ok_or_else(|| MoveNotationError::InvalidDirection {
+      found: format!("{dir:?}"),
+    })
+  }
+
+  /// # Errors
+  /// If any direction is invalid.
+  pub fn format_directions(self, dirs: &[Direction]) -> Result<String, MoveNotationError> {
+    let separator = match self {
+      Self::Numpad => "",
+      Self::Compass => " ",
+    };
+    let formatted = dirs
+      .iter()
+      .map(|&dir| self.format_direction(dir))
+      .collect::<Result<Vec<_>, _>>()?;
+    Ok(formatted.join(separator))
+  }
+
+  /// # Errors
+  /// If any direction is invalid.
+  pub fn format_moves(self, moves: &[(Direction, Amount)]) -> Result<String, MoveNotationError> {
+    let formatted = moves
+      .iter()
+      .map(|&(dir, amount)| Ok(format!("{}{amount}", self.format_direction(dir)?)))
+      .collect::<Result<Vec<_>, _>>()?;
+    Ok(formatted.join(" "))
+  }
+}
+
+fn tokens(str: &str) -> impl Iterator<Item = &str> {
+  str
+    .split(|char: char| char.is_whitespace() || char == ',')
+    .filter(|token| !token.is_empty())
+}
+
+fn parse_direction(token: &str) -> Result<Direction, MoveNotationError> {
+  let mut chars = token.chars();
+  let numpad_dir = match (chars.next(), chars.next()) {
+    (Some(digit), None) => Direction::from_numpad_digit(digit),
+    _ => None,
+  };
+  numpad_dir
+    .or_else(|| Direction::from_compass_name(token))
+    .ok_or_else(|| MoveNotationError::InvalidDirection {
+      found: token.to_string(),
+    })
+}
+
+/// Parses directions in any `MoveNotation`. Runs of numpad digits don't need separators.
+/// # Errors
+/// If a direction is invalid.
+pub fn parse_directions(str: &str) -> Result<Vec<Direction>, MoveNotationError> {
+  let mut dirs = Vec::new();
+  for token in tokens(str) {
+    if token.chars().all(|char| char.is_ascii_digit()) {
+      for digit in token.chars() {
+        dirs.push(parse_direction(digit.encode_utf8(&mut [0; 4]))?);
+      }
+    } else {
+      dirs.push(parse_direction(token)?);
+    }
+  }
+  Ok(dirs)
+}
+
+/// Parses moves with amounts in any `MoveNotation`.
+/// # Errors
+/// If a move is not a valid direction followed by a single digit amount.
+pub fn parse_moves(str: &str) -> Result<Vec<(Direction, Amount)>, MoveNotationError> {
+  tokens(str)
+    .map(|token| {
+      let invalid_move = || MoveNotationError::InvalidMove {
+        found: token.to_string(),
+      };
+      let (dir, amount) = token
+        .char_indices()
+        .last()
+        .map(|(index, _)| token.split_at(index))
+        .ok_or_else(invalid_move)?;
+      let amount = amount
+        .parse::<u8>()
+        .ok()
+        .and_then(|amount| Amount::try_from(amount).ok())
+        .ok_or_else(invalid_move)?;
+      Ok((parse_direction(dir)?, amount))
+    })
+    .collect()
+}
+
+/// `serde_with` adapter for move lists.
+///
+/// Serializes moves in compass `MoveNotation` and deserializes either notation
+/// or the plain `[[bits, amount], ...]` array used by `GameMeta`.
+///
+/// ```ignore
+/// #[serde_as(as = "Option<MoveList>")]
+/// moves: Option<Vec<(Direction, Amount)>>,
+/// ```
+pub struct MoveList;
+
+impl SerializeAs<Vec<(Direction, Amount)>> for MoveList {
+  fn serialize_as<S>(moves: &Vec<(Direction, Amount)>, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    let notation = MoveNotation::Compass
+      .format_moves(moves)
+      .map_err(ser::Error::custom)?;
+    serializer.serialize_str(&notation)
+  }
+}
+
+impl<'de> DeserializeAs<'de, Vec<(Direction, Amount)>> for MoveList {
+  fn deserialize_as<D>(deserializer: D) -> Result<Vec<(Direction, Amount)>, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    struct MoveListVisitor;
+
+    impl<'de> Visitor<'de> for MoveListVisitor {
+      type Value = Vec<(Direction, Amount)>;
+
+      fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a move notation string or an array of moves")
+      }
+
+      fn visit_str<E>(self, str: &str) -> Result<Self::Value, E>
+      where
+        E: de::Error,
+      {
+        parse_moves(str).map_err(E::custom)
+      }
+
+      fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+      where
+        A: SeqAccess<'de>,
+      {
+        Vec::deserialize(SeqAccessDeserializer::new(seq))
+      }
+    }
+
+    deserializer.deserialize_any(MoveListVisitor)
+  }
+}
@@ -0,0 +1,419 @@
+use super::{
+  Amount, Direction, GameField, GameState, Grid2D, MoveList, MultiplayerError, Playable,
+  PlayableError, Pos, RuleSet, Seed, Tile, TileGet, TileGrid, Topology,
+};
+use alloc::{format, string::String, sync::Arc, vec, vec::Vec};
+use core::fmt::{self, Display, Formatter};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+/// The `multiplayer_version` written by `MultiplayerMeta::new`.
+pub const MULTIPLAYER_FILE_VERSION: u64 = 1;
+
+/// A hot-seat game where players take turns on one shared game field.
+///
+/// Players are identified by the letters `A` to `Z` in turn order.
+/// Every player scores the tiles they consumed. A player is out once they can't move,
+/// the other players keep taking turns until nobody can move anymore.
+///
+/// Game field strings mark the start of every player with their letter instead of `@`:
+/// ```text
+/// 1A23
+/// 4321
+/// 12B3
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MultiplayerGame {
+  /// The shared mask. Its player pos is switched to the moving player before every move.
+  game_state: GameState,
+  starts: Vec<Pos>,
+  positions: Vec<Pos>,
+  names: Vec<String>,
+  scores: Vec<usize>,
+  /// The player of every move in `game_state.moves()`.
+  movers: Vec<usize>,
+  current: usize,
+}
+
+impl MultiplayerGame {
+  pub const MAX_PLAYERS: usize = 26;
+
+  /// Places a player on every start of `game_field`. The tiles at the starts are cleared.
+  /// # Errors
+  /// * If there are less than 2 or more than `MAX_PLAYERS` starts.
+  /// * If a start is off-board, a wall or used by multiple players.
+  pub fn new(game_field: GameField, starts: Vec<Pos>) -> Result<Self, MultiplayerError> {
+    if !(2..=Self::MAX_PLAYERS).contains(&starts.len()) {
+      return Err(MultiplayerError::PlayerCount {
+        found: starts.len(),
+      });
+    }
+    for (player, &pos) in starts.iter().enumerate() {
+      let is_valid = game_field.is_valid_pos(pos)
+        && game_field.is_playable(pos)
+        && !game_field.get_unchecked(pos).is_wall();
+      if !is_valid || starts[..player].contains(&pos) {
+        return Err(MultiplayerError::InvalidStart {
+          player: Self::player_char(player),
+          pos,
+        });
+      }
+    }
+
+    let player_count = starts.len();
+    let game_field = game_field.with_players(&starts);
+    let mut game = Self {
+      game_state: GameState::new(Arc::new(game_field)),
+      positions: starts.clone(),
+      starts,
+      names: (0..player_count)
+        .map(|player| format!("Player {}", Self::player_char(player)))
+        .collect(),
+      scores: vec![0; player_count],
+      movers: Vec::new(),
+      current: 0,
+    };
+    game.current = game.next_player(0).unwrap_or(0);
+    Ok(game)
+  }
+
+  /// Spreads `player_count` players evenly over the game field of `seed`.
+  /// The first player starts at the position the seed places the single player.
+  /// Every other player starts on the free cell closest to their evenly spread position,
+  /// never on a wall or an off-board cell.
+  /// # Errors
+  /// If `player_count` isn't supported or the game field is too small for it.
+  pub fn from_seed(seed: &Seed, player_count: usize) -> Result<Self, MultiplayerError> {
+    let game_field = GameField::from_seed(seed);
+    let cell_count = game_field.cell_count();
+    let first = game_field.pos_to_index_unchecked(game_field.player_pos());
+    let mut starts: Vec<Pos> = Vec::with_capacity(player_count);
+    for player in 0..player_count {
+      let target = game_field
+        .index_to_pos_unchecked((first + player * cell_count / player_count) % cell_count);
+      let start = (0..cell_count)
+        .map(|index| game_field.index_to_pos_unchecked(index))
+        .filter(|&pos| {
+          game_field.is_playable(pos)
+            && !game_field.get_unchecked(pos).is_wall()
+            && !starts.contains(&pos)
+        })
+        .min_by_key(|pos| (pos.x - target.x).unsigned_abs() + (pos.y - target.y).unsigned_abs())
+        // `new` reports that there is no start left
+        .unwrap_or(target);
+      starts.push(start);
+    }
+    Self::new(game_field, starts)
+  }
+
+  /// # Errors
+  /// If the amount of names doesn't match the amount of players.
+  pub fn with_names(mut self, names: Vec<String>) -> Result<Self, MultiplayerError> {
+    if names.len() != self.player_count() {
+      return Err(MultiplayerError::NameCount {
+        expected: self.player_count(),
+        found: names.len(),
+      });
+    }
+    self.names = names;
+    Ok(self)
+  }
+
+  /// The letter identifying `player`.
+  #[must_use]
+  #[allow(clippy::cast_possible_truncation)] // There are at most 26 players
+  pub fn player_char(player: usize) -> char {
+    char::from(b'A' + (player % Self::MAX_PLAYERS) as u8)
+  }
+
+  #[must_use]
+  pub fn player_count(&self) -> usize {
+    self.starts.len()
+  }
+
+  /// The player whose turn it is. Stays the last player to move once the game is complete.
+  #[must_use]
+  pub const fn current_player(&self) -> usize {
+    self.current
+  }
+
+  #[must_use]
+  pub fn name(&self, player: usize) -> Option<&str> {
+    self.names.get(player).map(String::as_str)
+  }
+
+  /// The amount of tiles `player` consumed.
+  #[must_use]
+  pub fn score(&self, player: usize) -> Option<usize> {
+    self.scores.get(player).copied()
+  }
+
+  #[must_use]
+  pub fn player_pos(&self, player: usize) -> Option<Pos> {
+    self.positions.get(player).copied()
+  }
+
+  /// If `player` can't move anymore.
+  #[must_use]
+  pub fn is_out(&self, player: usize) -> bool {
+    !self.can_move(player)
+  }
+
+  #[must_use]
+  pub fn is_game_complete(&self) -> bool {
+    (0..self.player_count()).all(|player| self.is_out(player))
+  }
+
+  /// The players with the highest score. Multiple players on a tie.
+  #[must_use]
+  pub fn leaders(&self) -> Vec<usize> {
+    let best = self.scores.iter().copied().max().unwrap_or(0);
+    (0..self.player_count())
+      .filter(|&player| self.scores[player] == best)
+      .collect()
+  }
+
+  /// All moves in the order they were made.
+  #[must_use]
+  pub fn moves(&self) -> &[(Direction, Amount)] {
+    self.game_state.moves()
+  }
+
+  /// The player of every move in `moves`.
+  #[must_use]
+  pub fn movers(&self) -> &[usize] {
+    &self.movers
+  }
+
+  /// The initial game field with every start cleared.
+  #[must_use]
+  pub fn game_field(&self) -> &GameField {
+    self.game_state.game_field()
+  }
+
+  /// Checks a move of the current player. See `Playable::check_move`.
+  /// # Errors
+  /// See `Playable::check_move`.
+  pub fn check_move(&self, dir: Direction) -> Result<Vec<usize>, PlayableError> {
+    self.game_state.move_path(self.positions[self.current], dir)
+  }
+
+  /// Moves the current player and passes the turn to the next player that can still move.
+  /// # Errors
+  /// See `Playable::check_move`.
+  pub fn move_(&mut self, dir: Direction) -> Result<Vec<usize>, PlayableError> {
+    let player = self.current;
+    self.game_state.set_player_pos(self.positions[player]);
+    let consumed = self.game_state.move_(dir)?;
+    self.positions[player] = self.game_state.player_pos();
+    self.scores[player] += consumed.len();
+    self.movers.push(player);
+    self.current = self.next_player(player + 1).unwrap_or(player);
+    Ok(consumed)
+  }
+
+  /// Undoes the last move of any player and gives the turn back to that player.
+  /// # Errors
+  /// See `Playable::undo_move`.
+  pub fn undo_move(&mut self) -> Result<(), PlayableError> {
+    let (&player, &(_, amount)) = self
+      .movers
+      .last()
+      .zip(self.game_state.moves().last())
+      .ok_or(PlayableError::BadMove)?;
+    self.game_state.set_player_pos(self.positions[player]);
+    self.game_state.undo_move()?;
+    self.movers.pop();
+    self.positions[player] = self.game_state.player_pos();
+    self.scores[player] -= usize::from(amount.amount());
+    self.current = player;
+    Ok(())
+  }
+
+  fn can_move(&self, player: usize) -> bool {
+    self.positions.get(player).is_some_and(|&pos| {
+      self
+        .game_state
+        .rule_set()
+        .directions()
+        .any(|dir| self.game_state.move_path(pos, dir).is_ok())
+    })
+  }
+
+  /// The first player starting at `from` in turn order that can move.
+  fn next_player(&self, from: usize) -> Option<usize> {
+    let player_count = self.player_count();
+    (0..player_count)
+      .map(|offset| (from + offset) % player_count)
+      .find(|&player| self.can_move(player))
+  }
+
+  /// # Errors
+  /// If `str` is neither a valid multiplayer save nor a multiplayer game field.
+  pub fn load_from_string(str: &str) -> Result<Self, MultiplayerError> {
+    if str.starts_with('{') {
+      let meta: MultiplayerMeta = serde_json::from_str(str)
+        .map_err(|err| MultiplayerError::InvalidMetaDataFromat { source: err.into() })?;
+      Self::load_from_meta(meta)
+    } else {
+      Self::try_from(str)
+    }
+  }
+
+  /// Creates the game and replays the moves of `meta`.
+  /// # Errors
+  /// If `meta` doesn't describe a valid game.
+  pub fn load_from_meta(meta: MultiplayerMeta) -> Result<Self, MultiplayerError> {
+    if meta.multiplayer_version > MULTIPLAYER_FILE_VERSION {
+      return Err(MultiplayerError::UnsupportedFileVersion {
+        found: meta.multiplayer_version,
+        supported: MULTIPLAYER_FILE_VERSION,
+      });
+    }
+    let game_field = meta
+      .game_field
+      .with_topology(meta.topology.unwrap_or_default())
+      .with_rule_set(meta.rule_set.unwrap_or_default());
+    let mut game = Self::new(game_field, meta.starts)?.with_names(meta.names)?;
+    for (move_number, (dir, amount)) in meta.moves.into_iter().enumerate() {
+      let consumed = game
+        .move_(dir)
+        .map_err(|_| MultiplayerError::InvalidMove { move_number })?;
+      if consumed.len() != usize::from(amount.amount()) {
+        return Err(MultiplayerError::InvalidMove { move_number });
+      }
+    }
+    Ok(game)
+  }
+
+  /// # Panics
+  /// Never, serializing `MultiplayerMeta` can't fail.
+  #[must_use]
+  pub fn save_to_string(&self) -> String {
+    serde_json::to_string(&MultiplayerMeta::new(self))
+      .expect("Serialize is never implemented manually and no Maps are used")
+  }
+}
+
+/// # Multiplayer save format
+///
+/// The turn order is derived from the moves, so every move is stored without its player.
+#[serde_as]
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct MultiplayerMeta {
+  pub multiplayer_version: u64,
+  /// The name of every player in turn order.
+  pub names: Vec<String>,
+  /// The start of every player in turn order.
+  pub starts: Vec<Pos>,
+  /// The initial game field. Missing `topology` and `rule_set` fall back to the defaults.
+  pub game_field: GameField,
+  pub topology: Option<Topology>,
+  pub rule_set: Option<RuleSet>,
+  #[serde_as(deserialize_as = "MoveList")]
+  pub moves: Vec<(Direction, Amount)>,
+}
+
+impl MultiplayerMeta {
+  #[must_use]
+  pub fn new(game: &MultiplayerGame) -> Self {
+    let game_field = game.game_field();
+    Self {
+      multiplayer_version: MULTIPLAYER_FILE_VERSION,
+      names: game.names.clone(),
+      starts: game.starts.clone(),
+      game_field: game_field.clone(),
+      topology: Some(game_field.topology()),
+      rule_set: Some(game_field.rule_set()),
+      moves: game.moves().to_vec(),
+    }
+  }
+}
+
+impl Display for MultiplayerGame {
+  /// Renders active players with their letter and players that are out in lowercase.
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    let x_size = self.game_state.dimensions().x_size;
+    for index in 0..self.game_state.cell_count() {
+      let pos = self.game_state.index_to_pos_unchecked(index);
+      if let Some(player) = self.positions.iter().position(|&it| it == pos) {
+        let char = Self::player_char(player);
+        if self.is_out(player) {
+          write!(f, "{}", char.to_ascii_lowercase())?;
+        } else {
+          write!(f, "{char}")?;
+        }
+      } else if self.game_state.is_playable(pos) {
+        write!(
+          f,
+          "{}",
+          Tile::from(self.game_state.get_fake_unchecked(index))
+        )?;
+      } else {
+        write!(f, "{}", GameField::OFF_BOARD_CHAR)?;
+      }
+      if (index + 1) % x_size == 0 {
+        writeln!(f)?;
+      }
+    }
+    Ok(())
+  }
+}
+
+impl TryFrom<&str> for MultiplayerGame {
+  type Error = MultiplayerError;
+
+  /// Parses a game field with player letters instead of `@`. Lowercase letters work as well.
+  fn try_from(value: &str) -> Result<Self, Self::Error> {
+    let mut starts: Vec<Option<Pos>> = Vec::new();
+    let mut field = String::with_capacity(value.len());
+    let (mut x, mut y) = (0, 0);
+    for c in value.chars() {
+      if c == '\n' {
+        (x, y) = (0, y + 1);
+        field.push(c);
+        continue;
+      }
+      if c.is_ascii_alphabetic() {
+        let player = usize::from(c.to_ascii_uppercase() as u8 - b'A');
+        let pos = Pos::new(x, y);
+        if starts.len() <= player {
+          starts.resize(player + 1, None);
+        }
+        if let Some(first) = starts[player] {
+          return Err(MultiplayerError::AmbiguousPlayer {
+            player: Self::player_char(player),
+            first,
+            second: pos,
+          });
+        }
+        // The game field needs exactly one player, the others are empty tiles
+        field.push(if starts.iter().flatten().next().is_none() {
+          char::from(Tile::Player)
+        } else {
+          char::from(Tile::EMPTY)
+        });
+        starts[player] = Some(pos);
+      } else {
+        field.push(c);
+      }
+      x += 1;
+    }
+
+    let starts = starts
+      .into_iter()
+      .enumerate()
+      .map(|(player, start)| {
+        start.ok_or(MultiplayerError::MissingPlayer {
+          player: Self::player_char(player),
+        })
+      })
+      .collect::<Result<Vec<_>, _>>()?;
+    if starts.len() < 2 {
+      return Err(MultiplayerError::PlayerCount {
+        found: starts.len(),
+      });
+    }
+    Self::new(GameField::try_from(field.as_str())?, starts)
+  }
+}
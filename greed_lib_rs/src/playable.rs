@@ -1,6 +1,6 @@
 use alloc::vec::Vec;
 
-use super::{Direction, GameField, PlayableError};
+use super::{CheckedMove, Direction, GameField, PlayableError, RuleSet};
 
 pub trait Playable {
   /// Checks if a move would be valid.
@@ -16,15 +16,53 @@ pub trait Playable {
   /// see `check_move`
   fn move_(&mut self, dir: Direction) -> Result<Vec<usize>, PlayableError>;
 
+  /// Like `check_move` but returns a `CheckedMove` that can be executed with `commit_move`.
+  /// # Errors
+  /// see `check_move`
+  fn checked_move(&self, dir: Direction) -> Result<CheckedMove, PlayableError> {
+    Ok(CheckedMove {
+      dir,
+      indices: self.check_move(dir)?,
+      origin: None,
+    })
+  }
+  /// Executes a move previously returned by `checked_move` without validating it again.
+  /// Returns the positions that were consumed like `check_move`.
+  ///
+  /// The default implementation can't tell states apart, so it checks the move again
+  /// and only executes it if it would still consume the same tiles.
+  /// # Errors
+  /// * If the state changed since the move was checked or the move was checked on another state.
+  fn commit_move(&mut self, checked_move: CheckedMove) -> Result<Vec<usize>, PlayableError> {
+    if self.check_move(checked_move.dir).as_deref() != Ok(checked_move.indices()) {
+      return Err(PlayableError::StaleCheckedMove);
+    }
+    self.move_(checked_move.dir)
+  }
+
   /// Undoes a previously executed Move
   /// # Errors
   /// * If no moves are left to undo.
   /// * If not enough data is left to undo the move
   fn undo_move(&mut self) -> Result<(), PlayableError>;
+  /// Redoes the last undone move.
+  /// Executing any other move clears the redo moves.
+  /// Returns the positions that were consumed like `check_move`.
+  /// # Errors
+  /// * If no moves are left to redo.
+  /// * If the move to redo is no longer valid
+  fn redo_move(&mut self) -> Result<Vec<usize>, PlayableError> {
+    // Without a redo stack there is never a move to redo
+    Err(PlayableError::BadMove)
+  }
   fn game_field(&self) -> &GameField;
   fn move_count(&self) -> usize;
+  /// The rules moves are checked with.
+  fn rule_set(&self) -> RuleSet {
+    self.game_field().rule_set()
+  }
   fn is_game_complete(&self) -> bool {
-    for dir in Direction::ALL_DIRECTIONS_CW {
+    for dir in self.rule_set().directions() {
       if self.check_move(dir) != Err(PlayableError::BadMove) {
         return false;
       }
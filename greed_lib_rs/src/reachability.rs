@@ -0,0 +1,104 @@
+use super::{Direction, GameState, Grid2D, Playable, Pos, TileGrid};
+use alloc::vec::Vec;
+use bitvec::prelude as bv;
+
+/// Over approximation of the tiles that can still be consumed from a `GameState`.
+///
+/// Tiles only ever become empty, so every move that is possible in the future is also possible
+/// if we pretend that no more tiles are consumed.
+/// All tiles touched by such moves that can be chained from the player pos are considered reachable.
+/// Every other non empty tile is dead and can never be consumed anymore.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reachability {
+  /// True for every non empty tile that might still be consumed.
+  reachable: bv::BitVec,
+  reachable_count: usize,
+  dead_count: usize,
+  max_score: usize,
+}
+
+impl Reachability {
+  #[must_use]
+  pub fn new(game_state: &GameState) -> Self {
+    let cell_count = game_state.cell_count();
+    let mut reachable = bv::bitvec![0; cell_count];
+    // Positions the player can stand on
+    let mut visited = bv::bitvec![0; cell_count];
+    let mut stack: Vec<Pos> = alloc::vec![game_state.player_pos()];
+    visited.set(
+      game_state.pos_to_index_unchecked(game_state.player_pos()),
+      true,
+    );
+
+    while let Some(pos) = stack.pop() {
+      for dir in game_state.rule_set().directions() {
+        let Some(end_pos) = Self::relaxed_move(game_state, pos, dir, &mut reachable) else {
+          continue;
+        };
+        let end_index = game_state.pos_to_index_unchecked(end_pos);
+        if !visited[end_index] {
+          visited.set(end_index, true);
+          stack.push(end_pos);
+        }
+      }
+    }
+
+    let reachable_count = reachable.count_ones();
+    let non_empty_count = game_state
+      .iter()
+      .filter(|tile| tile.amount().is_some_and(|amount| amount != 0))
+      .count();
+    let score = game_state.score();
+    Self {
+      reachable,
+      reachable_count,
+      dead_count: non_empty_count - reachable_count,
+      max_score: score + reachable_count,
+    }
+  }
+
+  /// Executes a move from `pos` on the current mask without consuming anything.
+  /// Marks the tiles the move would consume in `reachable` and returns the end position.
+  fn relaxed_move(
+    game_state: &GameState,
+    pos: Pos,
+    dir: Direction,
+    reachable: &mut bv::BitSlice,
+  ) -> Option<Pos> {
+    let path = game_state.move_path(pos, dir).ok()?;
+    for &index in &path {
+      reachable.set(index, true);
+    }
+    // A successful move consumes at least one tile
+    path
+      .last()
+      .map(|&index| game_state.index_to_pos_unchecked(index))
+  }
+
+  /// If the tile at `index` might still be consumed.
+  #[must_use]
+  pub fn is_reachable(&self, index: usize) -> bool {
+    self
+      .reachable
+      .get(index)
+      .is_some_and(|reachable| *reachable)
+  }
+
+  /// Amount of tiles that might still be consumed.
+  #[must_use]
+  pub const fn reachable_count(&self) -> usize {
+    self.reachable_count
+  }
+
+  /// Amount of non empty tiles that can never be consumed.
+  #[must_use]
+  pub const fn dead_count(&self) -> usize {
+    self.dead_count
+  }
+
+  /// Upper bound for the final score.
+  #[must_use]
+  pub const fn max_score(&self) -> usize {
+    self.max_score
+  }
+}
@@ -0,0 +1,160 @@
+use super::{GameMeta, Greed, SignatureError};
+use alloc::{string::String, vec::Vec};
+use core::fmt::Write;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+type ReplayDigest = [u8; 32];
+
+const DOMAIN: &[u8] = b"greed replay signature v2";
+const HMAC_BLOCK_SIZE: usize = 64;
+
+/// Tags separate the different kinds of chained data.
+#[repr(u8)]
+enum ChainTag {
+  Move = 1,
+  RedoMove = 2,
+  Timestamp = 3,
+  Final = 4,
+}
+
+fn hash_json(hasher: &mut Sha256, value: &impl Serialize) {
+  let json = serde_json::to_vec(value)
+    .expect("Serialize is never implemented manually and no Maps are used");
+  hasher.update((json.len() as u64).to_le_bytes());
+  hasher.update(json);
+}
+
+fn chain(digest: ReplayDigest, tag: ChainTag, value: &impl Serialize) -> ReplayDigest {
+  let mut hasher = Sha256::new();
+  hasher.update(digest);
+  hasher.update([tag as u8]);
+  hash_json(&mut hasher, value);
+  hasher.finalize().into()
+}
+
+pub(super) fn hmac_sha256(key: &[u8], message: &[u8]) -> ReplayDigest {
+  let mut block_key = [0; HMAC_BLOCK_SIZE];
+  if key.len() > HMAC_BLOCK_SIZE {
+    block_key[..32].copy_from_slice(&Sha256::digest(key));
+  } else {
+    block_key[..key.len()].copy_from_slice(key);
+  }
+  let inner = Sha256::new()
+    .chain_update(block_key.map(|byte| byte ^ 0x36))
+    .chain_update(message)
+    .finalize();
+  Sha256::new()
+    .chain_update(block_key.map(|byte| byte ^ 0x5c))
+    .chain_update(inner)
+    .finalize()
+    .into()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+  bytes.iter().fold(String::new(), |mut hex, byte| {
+    let _ = write!(hex, "{byte:02x}");
+    hex
+  })
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+  if hex.len() % 2 != 0 {
+    return None;
+  }
+  (0..hex.len())
+    .step_by(2)
+    .map(|index| u8::from_str_radix(hex.get(index..index + 2)?, 16).ok())
+    .collect()
+}
+
+impl GameMeta {
+  /// Hash chain over everything a replay consists of.
+  ///
+  /// The header (seed, challenge, topology, rule set, time limit, name, start time and
+  /// game fields) starts the chain.
+  /// Every move, redo move and timestamp is chained into the running digest in order.
+  /// The final digest also covers the score, time spent, idle time, undos and finish time.
+  ///
+  /// `file_version`, `greed_version`, `human_score` and `signature` are not covered
+  /// since they are rewritten or recomputed on load.
+  #[must_use]
+  pub fn replay_digest(&self) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(DOMAIN);
+    hash_json(&mut hasher, &self.seed);
+    hash_json(&mut hasher, &self.challenge);
+    hash_json(&mut hasher, &self.topology);
+    hash_json(&mut hasher, &self.rule_set);
+    hash_json(&mut hasher, &self.time_limit_ms);
+    hash_json(&mut hasher, &self.name);
+    hash_json(&mut hasher, &self.utc_started_ms);
+    hash_json(&mut hasher, &self.initial_game_field);
+    hash_json(&mut hasher, &self.last_game_field);
+    let mut digest: ReplayDigest = hasher.finalize().into();
+
+    for executed_move in self.moves.iter().flatten() {
+      digest = chain(digest, ChainTag::Move, executed_move);
+    }
+    for redo_move in self.redo_moves.iter().flatten() {
+      digest = chain(digest, ChainTag::RedoMove, redo_move);
+    }
+    for timestamp in self.move_timestamps_ms.iter().flatten() {
+      digest = chain(digest, ChainTag::Timestamp, timestamp);
+    }
+    chain(
+      digest,
+      ChainTag::Final,
+      &(
+        self.moves.as_ref().map(Vec::len),
+        self.redo_moves.as_ref().map(Vec::len),
+        self.move_timestamps_ms.as_ref().map(Vec::len),
+        self.score,
+        self.time_spent_ms,
+        self.idle_ms,
+        self.undos,
+        self.utc_finished_ms,
+      ),
+    )
+  }
+
+  /// Signs the `replay_digest` with HMAC-SHA256 and stores it as hex in `signature`.
+  pub fn sign(&mut self, key: &[u8]) {
+    self.signature = Some(to_hex(&hmac_sha256(key, &self.replay_digest())));
+  }
+
+  /// Verifies that `signature` was created with `key` for exactly this replay.
+  /// # Errors
+  /// * If there is no signature or it isn't valid hex.
+  /// * If any covered field was edited after signing or the key is wrong.
+  pub fn verify_signature(&self, key: &[u8]) -> Result<(), SignatureError> {
+    let signature = self
+      .signature
+      .as_deref()
+      .ok_or(SignatureError::MissingSignature)?;
+    let signature = from_hex(signature).ok_or(SignatureError::MalformedSignature)?;
+    let expected = hmac_sha256(key, &self.replay_digest());
+    // Compare in constant time to not leak how many bytes are correct
+    let difference = signature
+      .iter()
+      .zip(expected)
+      .fold(0, |difference, (a, b)| difference | (a ^ b));
+    if signature.len() != expected.len() || difference != 0 {
+      return Err(SignatureError::SignatureMismatch);
+    }
+    Ok(())
+  }
+}
+
+impl Greed {
+  /// Like `save_to_string` but signs the save with `key`. See `GameMeta::sign`.
+  /// # Panics
+  /// Never, serializing `GameMeta` can't fail.
+  #[must_use]
+  pub fn save_to_string_signed(&self, key: &[u8]) -> String {
+    let mut meta = GameMeta::new(self);
+    meta.sign(key);
+    serde_json::to_string(&meta)
+      .expect("Serialize is never implemented manually and no Maps are used")
+  }
+}
@@ -0,0 +1,184 @@
+use super::{Direction, Pos};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+#[error(
+  "Rule set vector {vector} is zero or longer than {} in a direction",
+  RuleSet::MAX_VECTOR_LENGTH
+)]
+pub struct RuleSetConversionError {
+  pub vector: Pos,
+}
+
+/// How the amount of tiles a move consumes is derived from the tiles in its direction.
+///
+/// `values` are the amounts of the tiles from the closest to the farthest.
+/// A move consumes the shortest run of non empty tiles for which the rule holds.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TravelDistance {
+  /// The value of the first tile is the distance. The classic rule.
+  #[default]
+  FirstTile,
+  /// The value of the last tile is the distance.
+  LastTile,
+  /// The sum of all consumed tiles mod 9 is the distance mod 9.
+  SumMod9,
+}
+
+impl TravelDistance {
+  /// The maximum distance of a move. It always fits into an `Amount`.
+  pub const MAX_DISTANCE: usize = 9;
+
+  /// If a move over the tiles with `values` ends at the last one.
+  #[must_use]
+  pub fn ends_at(self, values: &[u8]) -> bool {
+    let distance = values.len();
+    match self {
+      Self::FirstTile => values
+        .first()
+        .is_some_and(|&first| usize::from(first) == distance),
+      Self::LastTile => values
+        .last()
+        .is_some_and(|&last| usize::from(last) == distance),
+      Self::SumMod9 => {
+        !values.is_empty()
+          && values
+            .iter()
+            .map(|&value| usize::from(value))
+            .sum::<usize>()
+            % 9
+            == distance % 9
+      },
+    }
+  }
+}
+
+/// The rules a game is played with.
+///
+/// Every `Direction` in `Direction::ALL_DIRECTIONS_CW` is a slot that can be disabled or
+/// mapped to any vector. Moves are still recorded with the direction of their slot,
+/// so saves and `MoveNotation` work for every rule set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedRuleSet")]
+pub struct RuleSet {
+  /// The vector of every slot in `Direction::ALL_DIRECTIONS_CW` order. `None` if the slot is disabled.
+  vectors: [Option<Pos>; 8],
+  travel_distance: TravelDistance,
+}
+
+/// A deserialized `RuleSet` whose vectors weren't checked yet.
+#[derive(Deserialize)]
+struct UncheckedRuleSet {
+  vectors: [Option<Pos>; 8],
+  travel_distance: TravelDistance,
+}
+
+impl TryFrom<UncheckedRuleSet> for RuleSet {
+  type Error = RuleSetConversionError;
+
+  fn try_from(value: UncheckedRuleSet) -> Result<Self, Self::Error> {
+    Ok(Self::try_custom(value.vectors)?.with_travel_distance(value.travel_distance))
+  }
+}
+
+impl RuleSet {
+  /// The maximum of `|x|` and `|y|` of a vector.
+  pub const MAX_VECTOR_LENGTH: usize = 9;
+
+  /// The classic rules. Moves in all 8 directions.
+  pub const EIGHT_WAY: Self = Self::custom([
+    Some(Pos::new(0, -1)),
+    Some(Pos::new(1, -1)),
+    Some(Pos::new(1, 0)),
+    Some(Pos::new(1, 1)),
+    Some(Pos::new(0, 1)),
+    Some(Pos::new(-1, 1)),
+    Some(Pos::new(-1, 0)),
+    Some(Pos::new(-1, -1)),
+  ]);
+  /// Only `UP`, `RIGHT`, `DOWN` and `LEFT`.
+  pub const ORTHOGONAL: Self = Self::custom([
+    Some(Pos::new(0, -1)),
+    None,
+    Some(Pos::new(1, 0)),
+    None,
+    Some(Pos::new(0, 1)),
+    None,
+    Some(Pos::new(-1, 0)),
+    None,
+  ]);
+  /// Chess knight jumps. `UP` jumps two up and one right, the other slots follow clockwise.
+  pub const KNIGHT: Self = Self::custom([
+    Some(Pos::new(1, -2)),
+    Some(Pos::new(2, -1)),
+    Some(Pos::new(2, 1)),
+    Some(Pos::new(1, 2)),
+    Some(Pos::new(-1, 2)),
+    Some(Pos::new(-2, 1)),
+    Some(Pos::new(-2, -1)),
+    Some(Pos::new(-1, -2)),
+  ]);
+
+  /// `vectors` in `Direction::ALL_DIRECTIONS_CW` order. `None` disables a direction.
+  /// Vectors aren't checked, see `try_custom`.
+  #[must_use]
+  pub const fn custom(vectors: [Option<Pos>; 8]) -> Self {
+    Self {
+      vectors,
+      travel_distance: TravelDistance::FirstTile,
+    }
+  }
+
+  /// Like `custom` but checks the vectors.
+  /// # Errors
+  /// If a vector is zero or `|x|` or `|y|` is bigger than `MAX_VECTOR_LENGTH`.
+  pub fn try_custom(vectors: [Option<Pos>; 8]) -> Result<Self, RuleSetConversionError> {
+    let invalid = vectors.into_iter().flatten().find(|vector| {
+      *vector == Pos::new(0, 0)
+        || vector.x.unsigned_abs() > Self::MAX_VECTOR_LENGTH
+        || vector.y.unsigned_abs() > Self::MAX_VECTOR_LENGTH
+    });
+    match invalid {
+      Some(vector) => Err(RuleSetConversionError { vector }),
+      None => Ok(Self::custom(vectors)),
+    }
+  }
+
+  #[must_use]
+  pub const fn with_travel_distance(mut self, travel_distance: TravelDistance) -> Self {
+    self.travel_distance = travel_distance;
+    self
+  }
+
+  #[must_use]
+  pub const fn travel_distance(&self) -> TravelDistance {
+    self.travel_distance
+  }
+
+  #[must_use]
+  pub const fn vectors(&self) -> &[Option<Pos>; 8] {
+    &self.vectors
+  }
+
+  /// The vector one step in `dir` moves. `None` if `dir` is invalid or disabled.
+  #[must_use]
+  pub fn vector(&self, dir: Direction) -> Option<Pos> {
+    self.vectors[dir.cw_index()?]
+  }
+
+  /// All enabled directions in `Direction::ALL_DIRECTIONS_CW` order.
+  pub fn directions(self) -> impl Iterator<Item = Direction> {
+    Direction::ALL_DIRECTIONS_CW
+      .into_iter()
+      .zip(self.vectors)
+      .filter_map(|(dir, vector)| vector.map(|_| dir))
+  }
+}
+
+impl Default for RuleSet {
+  fn default() -> Self {
+    Self::EIGHT_WAY
+  }
+}
@@ -0,0 +1,82 @@
+use super::{GameMeta, GreedParserError};
+use alloc::string::String;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+/// The `file_version` written by `GameMeta::new`.
+///
+/// Only bump it if the layout changes in a way older versions can't read,
+/// not for new optional fields, and add a step to `MIGRATIONS`.
+///
+/// Version history:
+/// * 1: Current layout. Files written before versioning have no `file_version` but the same
+///   layout and are read as version 1.
+pub const CURRENT_FILE_VERSION: u64 = 1;
+
+/// The version of files without a `file_version`.
+const UNVERSIONED_FILE_VERSION: u64 = 1;
+
+/// Upgrades the layout of `version` to `version + 1`.
+pub(crate) type MigrationStep = fn(&mut Map<String, Value>);
+
+/// `MIGRATIONS[version - 1]` upgrades a file from `version` to `version + 1`.
+#[allow(clippy::cast_possible_truncation)]
+const MIGRATIONS: [MigrationStep; (CURRENT_FILE_VERSION - 1) as usize] = [];
+
+/// Only the `file_version` is read first to decide if the save needs migrating.
+#[derive(Deserialize)]
+struct FileVersion {
+  #[serde(default)]
+  file_version: Option<u64>,
+}
+
+/// Upgrades serialized `GameMeta` of any known `file_version` to `CURRENT_FILE_VERSION` and
+/// deserializes it. Files without a `file_version` are treated as version 1.
+/// Saves of the current version are deserialized directly, only older ones go through a `Value`.
+/// # Errors
+/// * If `json` isn't a json object or doesn't match the layout of its version.
+/// * If `file_version` is newer than `CURRENT_FILE_VERSION`.
+pub fn migrate(json: &str) -> Result<GameMeta, GreedParserError> {
+  let FileVersion { file_version } = serde_json::from_str(json)
+    .map_err(|err| GreedParserError::InvalidMetaDataFromat { source: err.into() })?;
+  let file_version = file_version.unwrap_or(UNVERSIONED_FILE_VERSION);
+  if file_version > CURRENT_FILE_VERSION {
+    return Err(GreedParserError::UnsupportedFileVersion {
+      found: file_version,
+      supported: CURRENT_FILE_VERSION,
+    });
+  }
+
+  if file_version == CURRENT_FILE_VERSION {
+    let mut game_meta: GameMeta = serde_json::from_str(json)
+      .map_err(|err| GreedParserError::InvalidMetaDataFromat { source: err.into() })?;
+    game_meta.file_version = Some(CURRENT_FILE_VERSION);
+    return Ok(game_meta);
+  }
+  let value = serde_json::from_str(json)
+    .map_err(|err| GreedParserError::InvalidMetaDataFromat { source: err.into() })?;
+  migrate_value(value, file_version, &MIGRATIONS)
+}
+
+/// Runs the `migrations` starting at `file_version` on `value` and deserializes the result.
+pub(crate) fn migrate_value(
+  value: Value,
+  file_version: u64,
+  migrations: &[MigrationStep],
+) -> Result<GameMeta, GreedParserError> {
+  let Value::Object(mut game_meta) = value else {
+    // Let serde report what's wrong
+    return serde_json::from_value(value)
+      .map_err(|err| GreedParserError::InvalidMetaDataFromat { source: err.into() });
+  };
+
+  // An explicit version 0 was never written and is read like a missing version
+  #[allow(clippy::cast_possible_truncation)] // Bounded by CURRENT_FILE_VERSION
+  for step in &migrations[file_version.saturating_sub(1) as usize..] {
+    step(&mut game_meta);
+  }
+  game_meta.insert("file_version".into(), CURRENT_FILE_VERSION.into());
+
+  serde_json::from_value(Value::Object(game_meta))
+    .map_err(|err| GreedParserError::InvalidMetaDataFromat { source: err.into() })
+}
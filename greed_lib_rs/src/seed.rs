@@ -1,4 +1,7 @@
-use super::{Size2D, Size2DConversionError, TileProbs, TileProbsConversionError};
+use super::{
+  BoardGeneratorId, BoardGeneratorIdConversionError, Size2D, Size2DConversionError, TileProbs,
+  TileProbsConversionError, Topology, TopologyConversionError,
+};
 use alloc::{fmt, format, string::String};
 use arbitrary::Arbitrary;
 use core::fmt::{Debug, Display, Formatter, Write};
@@ -33,6 +36,16 @@ pub enum SeedConversionError {
     #[from]
     source: TileProbsConversionError,
   },
+  #[error("Invalid topology")]
+  InvalidTopology {
+    #[from]
+    source: TopologyConversionError,
+  },
+  #[error("Invalid board generator")]
+  InvalidGenerator {
+    #[from]
+    source: BoardGeneratorIdConversionError,
+  },
   #[error("Unexpected hash tag")]
   UnexpectedHashTag,
   #[error("Unexpected end of the Seed")]
@@ -67,8 +80,13 @@ impl UserString {
   pub fn as_str(&self) -> &str {
     &self.0[..]
   }
+  /// Uses `rand::thread_rng`. Requires the `thread_rng` feature.
+  #[cfg(feature = "thread_rng")]
   pub fn new_random() -> Self {
-    let inner = rand::thread_rng()
+    Self::new_random_with_rng(&mut rand::thread_rng())
+  }
+  pub fn new_random_with_rng(rng: &mut impl Rng) -> Self {
+    let inner = rng
       .sample_iter(Self::RANDOM_USER_STRING_DISTRIBUTION)
       .map(|byte| byte as char)
       .take(Self::RANDOM_USER_STRING_LENGTH)
@@ -121,7 +139,8 @@ impl<'a> Arbitrary<'a> for UserString {
 
 /// # Seed format yummy:
 ///
-/// The seed encodes the `user_str` and optionally the `size` and the `tile_probabilities` for all tiles.
+/// The seed encodes the `user_str` and optionally the `size`, the `tile_probabilities` for all tiles,
+/// the `topology` and the board `generator`.
 /// The dimensions and probabilies are encoded as ~`upper_alternating_case`~ hex.
 ///
 /// `\#` is used as a separator
@@ -130,21 +149,27 @@ impl<'a> Arbitrary<'a> for UserString {
 ///
 /// `[]` indicates optional
 ///
-/// Format: `<user_str>[#<x_size>x<y_size>[#112233445566778899]]`
+/// Format: `<user_str>[#<x_size>x<y_size>[#[112233445566778899][#[<topology>][#<generator>]]]]`
 ///
 /// Representation:
 /// * `user_str: A-Za-z0-9_`
 /// * `x_size: unsigned`
 /// * `y_size: unsigned`
 /// * \<T>`XX: probability of tile T as two hex digits` where `T is the tile number in 1..=9`
+///   optionally followed by the probability of walls: `112233445566778899AA`
+/// * `topology: bounded | torus` an empty probability part keeps the default probabilities: `abc#4fx15##torus`
+/// * `generator: uniform | clustered | symmetric | high_clearance` an empty topology part keeps the
+///   default topology: `abc#4fx15###clustered`. Seeds without a generator use `uniform`.
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(try_from = "&str")]
+#[serde(try_from = "String")]
 #[serde(into = "String")]
 #[must_use]
 pub struct Seed {
   tile_probabilities: TileProbs,
   size: Size2D,
   user_str: UserString,
+  topology: Topology,
+  generator: BoardGeneratorId,
 }
 
 impl Seed {
@@ -154,16 +179,41 @@ impl Seed {
       tile_probabilities: tile_probabilities.unwrap_or(TileProbs::DEFAULT_TILE_PROBABILITIES),
       size,
       user_str,
+      topology: Topology::Bounded,
+      generator: BoardGeneratorId::Uniform,
     }
   }
   /// `tile_probabilities` == None uses: `DEFAULT_TILE_PROBABILITIES`
+  ///
+  /// Uses `rand::thread_rng`. Requires the `thread_rng` feature.
+  #[cfg(feature = "thread_rng")]
   pub fn new_random(size: Size2D, tile_probabilities: Option<TileProbs>) -> Self {
+    Self::new_random_with_rng(size, tile_probabilities, &mut rand::thread_rng())
+  }
+  /// `tile_probabilities` == None uses: `DEFAULT_TILE_PROBABILITIES`
+  ///
+  /// The same `rng` state always produces the same seed.
+  pub fn new_random_with_rng(
+    size: Size2D,
+    tile_probabilities: Option<TileProbs>,
+    rng: &mut impl Rng,
+  ) -> Self {
     Self {
       tile_probabilities: tile_probabilities.unwrap_or(TileProbs::DEFAULT_TILE_PROBABILITIES),
       size,
-      user_str: UserString::new_random(),
+      user_str: UserString::new_random_with_rng(rng),
+      topology: Topology::Bounded,
+      generator: BoardGeneratorId::Uniform,
     }
   }
+  pub const fn with_topology(mut self, topology: Topology) -> Self {
+    self.topology = topology;
+    self
+  }
+  pub const fn with_generator(mut self, generator: BoardGeneratorId) -> Self {
+    self.generator = generator;
+    self
+  }
   #[must_use]
   pub fn user_str(&self) -> &str {
     &self.user_str.0
@@ -176,7 +226,17 @@ impl Seed {
   pub const fn tile_probabilities(&self) -> &TileProbs {
     &self.tile_probabilities
   }
-  fn partial_verify(value: &str) -> Result<(&str, Size2D, TileProbs), SeedConversionError> {
+  #[must_use]
+  pub const fn topology(&self) -> Topology {
+    self.topology
+  }
+  #[must_use]
+  pub const fn generator(&self) -> BoardGeneratorId {
+    self.generator
+  }
+  fn partial_verify(
+    value: &str,
+  ) -> Result<(&str, Size2D, TileProbs, Topology, BoardGeneratorId), SeedConversionError> {
     if value.is_empty() {
       return Err(SeedConversionError::EmptyString);
     }
@@ -190,14 +250,32 @@ impl Seed {
     let tile_probabilities_slice = parts.next();
 
     let tile_probabilities = tile_probabilities_slice
+      .filter(|slice| !slice.is_empty())
       .map(TileProbs::try_from)
       .transpose()?
       .unwrap_or(TileProbs::DEFAULT_TILE_PROBABILITIES);
+    let topology = parts
+      .next()
+      .filter(|slice| !slice.is_empty())
+      .map(Topology::try_from)
+      .transpose()?
+      .unwrap_or_default();
+    let generator = parts
+      .next()
+      .map(BoardGeneratorId::try_from)
+      .transpose()?
+      .unwrap_or_default();
 
     if parts.next().is_some() {
       return Err(SeedConversionError::UnexpectedHashTag);
     }
-    Ok((user_str_slice, size, tile_probabilities))
+    Ok((
+      user_str_slice,
+      size,
+      tile_probabilities,
+      topology,
+      generator,
+    ))
   }
 }
 
@@ -207,13 +285,31 @@ impl Display for Seed {
       size: Size2D { x_size, y_size },
       user_str,
       tile_probabilities,
+      topology,
+      generator,
     } = self;
     write!(f, "{user_str}#{x_size:x}x{y_size:x}")?;
-    if *tile_probabilities != TileProbs::DEFAULT_TILE_PROBABILITIES {
+    let default_generator = *generator == BoardGeneratorId::Uniform;
+    // The topology part is also needed as separator before the generator
+    let default_tail = *topology == Topology::Bounded && default_generator;
+    if *tile_probabilities != TileProbs::DEFAULT_TILE_PROBABILITIES || !default_tail {
       f.write_char('#')?;
+    }
+    if *tile_probabilities != TileProbs::DEFAULT_TILE_PROBABILITIES {
       for prob in tile_probabilities {
         write!(f, "{prob:02x}")?;
       }
+      if tile_probabilities.wall() != 0 {
+        write!(f, "{:02x}", tile_probabilities.wall())?;
+      }
+    }
+    if *topology != Topology::Bounded {
+      write!(f, "#{topology}")?;
+    } else if !default_generator {
+      f.write_char('#')?;
+    }
+    if !default_generator {
+      write!(f, "#{generator}")?;
     }
     Ok(())
   }
@@ -239,12 +335,15 @@ impl TryFrom<&str> for Seed {
   type Error = SeedConversionError;
 
   fn try_from(value: &str) -> Result<Self, Self::Error> {
-    let (user_str_slice, size, tile_probabilities) = Self::partial_verify(value)?;
+    let (user_str_slice, size, tile_probabilities, topology, generator) =
+      Self::partial_verify(value)?;
     let user_str = UserString::try_from(String::from(user_str_slice))?;
     Ok(Self {
       tile_probabilities,
       size,
       user_str,
+      topology,
+      generator,
     })
   }
 }
@@ -253,13 +352,16 @@ impl TryFrom<String> for Seed {
   type Error = SeedConversionError;
 
   fn try_from(mut value: String) -> Result<Self, Self::Error> {
-    let (user_str_slice, size, tile_probabilities) = Self::partial_verify(&value)?;
+    let (user_str_slice, size, tile_probabilities, topology, generator) =
+      Self::partial_verify(&value)?;
     value.truncate(user_str_slice.len());
     let user_str = UserString::try_from(value)?;
     Ok(Self {
       tile_probabilities,
       size,
       user_str,
+      topology,
+      generator,
     })
   }
 }
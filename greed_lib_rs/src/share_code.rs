@@ -0,0 +1,203 @@
+use super::{
+  parse_directions, Direction, Greed, GreedBuilder, MoveNotation, MoveNotationError, Playable,
+  PlayableError, RuleSet, Seed, SeedConversionError, SharedClock, TileGrid,
+};
+use alloc::{format, string::String, vec::Vec};
+use core::fmt::{self, Debug, Display, Formatter, Write};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ShareCodeError {
+  #[error("Expected 4 parts separated by '.' found {found}")]
+  WrongPartCount { found: usize },
+  #[error("Checksum mismatch (the code was probably mistyped)")]
+  ChecksumMismatch,
+  #[error("Invalid seed")]
+  InvalidSeed {
+    #[from]
+    source: SeedConversionError,
+  },
+  #[error("Invalid moves")]
+  InvalidMoves {
+    #[from]
+    source: MoveNotationError,
+  },
+  #[error("Invalid target score")]
+  InvalidTargetScore,
+}
+
+/// # Share code format:
+///
+/// Bundles a `Seed`, an optional move prefix and an optional target score.
+///
+/// Format: `<seed>.<moves>.<target_score>.<checksum>`
+///
+/// * `seed`: The `Seed` with every `#` replaced by `-`
+/// * `moves`: Numpad `MoveNotation` without amounts, may be empty
+/// * `target_score`: decimal, may be empty
+/// * `checksum`: The first 3 bytes of the Sha256 of everything before the last `.` as hex
+///
+/// Example: `abc-4fx15.8963.1843.1f2e3d`
+///
+/// Share codes only contain `A-Za-z0-9_-.` (unless the user string contains non ascii letters)
+/// and can be used in URLs as they are.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ShareCode {
+  seed: Seed,
+  /// Moves to execute after generating the game field.
+  moves: Vec<Direction>,
+  /// The score to beat.
+  target_score: Option<usize>,
+}
+
+impl ShareCode {
+  const SEPARATOR: char = '.';
+  const SEED_SEPARATOR: char = '-';
+  const CHECKSUM_BYTES: usize = 3;
+
+  /// # Errors
+  /// If any direction is invalid.
+  pub fn new(
+    seed: Seed,
+    moves: Vec<Direction>,
+    target_score: Option<usize>,
+  ) -> Result<Self, ShareCodeError> {
+    if let Some(&dir) = moves.iter().find(|dir| !dir.is_valid()) {
+      return Err(
+        MoveNotationError::InvalidDirection {
+          found: format!("{dir:?}"),
+        }
+        .into(),
+      );
+    }
+    Ok(Self {
+      seed,
+      moves,
+      target_score,
+    })
+  }
+
+  /// Shares the moves and score of `greed` as challenge.
+  /// `None` if the game has no seed or isn't played with `RuleSet::EIGHT_WAY`,
+  /// since share codes can't store the rule set.
+  #[must_use]
+  pub fn from_greed(greed: &Greed) -> Option<Self> {
+    if greed.rule_set() != RuleSet::EIGHT_WAY {
+      return None;
+    }
+    Some(Self {
+      seed: greed.seed()?.clone(),
+      moves: greed
+        .game_state()
+        .moves()
+        .iter()
+        .map(|&(dir, _)| dir)
+        .collect(),
+      target_score: Some(greed.score()),
+    })
+  }
+
+  #[must_use]
+  pub const fn seed(&self) -> &Seed {
+    &self.seed
+  }
+
+  #[must_use]
+  pub fn moves(&self) -> &[Direction] {
+    &self.moves
+  }
+
+  #[must_use]
+  pub const fn target_score(&self) -> Option<usize> {
+    self.target_score
+  }
+
+  /// Creates the game with `RuleSet::EIGHT_WAY` and executes the move prefix.
+  /// # Errors
+  /// If a move can't be executed.
+  pub fn build_greed(&self) -> Result<Greed, PlayableError> {
+    self.build_greed_with_clock(SharedClock::default())
+  }
+
+  /// Like `build_greed` but measures the time with `clock`.
+  /// # Errors
+  /// See `build_greed`
+  pub fn build_greed_with_clock(&self, clock: SharedClock) -> Result<Greed, PlayableError> {
+    let mut builder = GreedBuilder::new().seed(self.seed.clone());
+    builder.clock(clock);
+    let mut greed = builder.build();
+    for &dir in &self.moves {
+      greed.move_(dir)?;
+    }
+    Ok(greed)
+  }
+
+  fn checksum(body: &str) -> String {
+    let hash = Sha256::digest(body);
+    hash[..Self::CHECKSUM_BYTES]
+      .iter()
+      .fold(String::new(), |mut checksum, byte| {
+        let _ = write!(checksum, "{byte:02x}");
+        checksum
+      })
+  }
+
+  fn body(&self) -> Result<String, MoveNotationError> {
+    let seed = String::from(&self.seed).replace('#', "-");
+    let moves = MoveNotation::Numpad.format_directions(&self.moves)?;
+    let target_score = self
+      .target_score
+      .map_or_else(String::new, |target_score| format!("{target_score}"));
+    let separator = Self::SEPARATOR;
+    Ok(format!("{seed}{separator}{moves}{separator}{target_score}"))
+  }
+}
+
+impl Display for ShareCode {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    // Only valid directions are accepted by the constructors so formatting can't fail
+    let body = self.body().map_err(|_| fmt::Error)?;
+    write!(f, "{body}{}{}", Self::SEPARATOR, Self::checksum(&body))
+  }
+}
+
+impl Debug for ShareCode {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "{self}")
+  }
+}
+
+impl TryFrom<&str> for ShareCode {
+  type Error = ShareCodeError;
+
+  fn try_from(value: &str) -> Result<Self, Self::Error> {
+    let value = value.trim();
+    let parts: Vec<&str> = value.split(Self::SEPARATOR).collect();
+    let &[seed, moves, target_score, checksum] = parts.as_slice() else {
+      return Err(ShareCodeError::WrongPartCount { found: parts.len() });
+    };
+
+    let body = &value[..value.len() - checksum.len() - 1];
+    if !checksum.eq_ignore_ascii_case(&Self::checksum(body)) {
+      return Err(ShareCodeError::ChecksumMismatch);
+    }
+
+    let seed = Seed::try_from(seed.replace(Self::SEED_SEPARATOR, "#"))?;
+    let moves = parse_directions(moves)?;
+    let target_score = if target_score.is_empty() {
+      None
+    } else {
+      Some(
+        target_score
+          .parse()
+          .map_err(|_| ShareCodeError::InvalidTargetScore)?,
+      )
+    };
+    Ok(Self {
+      seed,
+      moves,
+      target_score,
+    })
+  }
+}
@@ -0,0 +1,258 @@
+use super::{
+  Amount, Direction, GameField, GameState, Grid2D, Playable, Reachability, SharedClock, TileGrid,
+};
+use alloc::{
+  collections::{BTreeMap, BTreeSet},
+  sync::Arc,
+  vec::Vec,
+};
+use bitvec::prelude as bv;
+use chrono::{DateTime, Duration, Utc};
+
+/// Limits and tuning knobs for `Solver`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SolverConfig {
+  /// Maximum amount of moves the solver executes while searching.
+  pub node_budget: usize,
+  /// Maximum wall clock time the solver may use. `None` means only `node_budget` limits the search.
+  /// The exhaustive search may use half of it, the beam search gets the rest.
+  pub time_budget: Option<Duration>,
+  /// Boards with at most this many tiles are searched exhaustively first.
+  pub exhaustive_tile_limit: usize,
+  /// How many states the beam search keeps per move.
+  pub beam_width: usize,
+  /// Measures the `time_budget`.
+  pub clock: SharedClock,
+}
+
+impl SolverConfig {
+  pub const DEFAULT_NODE_BUDGET: usize = 200_000;
+  pub const DEFAULT_EXHAUSTIVE_TILE_LIMIT: usize = 100;
+  pub const DEFAULT_BEAM_WIDTH: usize = 64;
+}
+
+impl Default for SolverConfig {
+  fn default() -> Self {
+    Self {
+      node_budget: Self::DEFAULT_NODE_BUDGET,
+      time_budget: None,
+      exhaustive_tile_limit: Self::DEFAULT_EXHAUSTIVE_TILE_LIMIT,
+      beam_width: Self::DEFAULT_BEAM_WIDTH,
+      clock: SharedClock::default(),
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Solution {
+  /// The moves starting from the solved state. Can be appended to `GameMeta.moves`.
+  pub moves: Vec<(Direction, Amount)>,
+  /// The score after executing all moves.
+  pub score: usize,
+  /// True if no other move sequence reaches a higher score.
+  pub proven_optimal: bool,
+  /// The amount of moves executed while searching.
+  pub nodes: usize,
+}
+
+/// Searches for the move sequence with the maximum `score()`.
+///
+/// Small boards are solved with an exhaustive memoized depth first search.
+/// If the board is too big or the search runs out of budget a beam search is used instead.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Solver {
+  config: SolverConfig,
+}
+
+/// Identifies a game state independent of the moves that lead to it.
+type StateKey = (bv::BitVec, usize);
+
+struct Search<'a> {
+  config: &'a SolverConfig,
+  started: DateTime<Utc>,
+  /// The time budget of the current phase, measured from `started`.
+  time_budget: Option<Duration>,
+  nodes: usize,
+  /// The best amount of tiles that can still be consumed and the first move to do so.
+  memo: BTreeMap<StateKey, (usize, Option<Direction>)>,
+}
+
+impl Search<'_> {
+  fn out_of_budget(&self) -> bool {
+    if self.nodes >= self.config.node_budget {
+      return true;
+    }
+    // Checking the clock is a lot more expensive than a move.
+    // Skipping it before the first move makes sure every phase finds at least one move.
+    self.nodes > 0
+      && self.nodes % 1024 == 0
+      && self
+        .time_budget
+        .is_some_and(|time_budget| self.config.clock.now() - self.started > time_budget)
+  }
+
+  fn state_key(game_state: &GameState) -> StateKey {
+    (
+      game_state.mask().clone(),
+      game_state.pos_to_index_unchecked(game_state.player_pos()),
+    )
+  }
+
+  /// Returns the maximum amount of tiles that can still be consumed or `None` if the budget ran out.
+  /// `limit` is an upper bound for the result. Once it is reached no other moves are tried.
+  fn dfs(&mut self, game_state: &mut GameState, limit: usize) -> Option<usize> {
+    let key = Self::state_key(game_state);
+    if let Some(&(best, _)) = self.memo.get(&key) {
+      return Some(best);
+    }
+
+    let mut best = (0, None);
+    for dir in game_state.rule_set().directions() {
+      if best.0 == limit {
+        break;
+      }
+      if self.out_of_budget() {
+        return None;
+      }
+      let Ok(consumed) = game_state.move_(dir) else {
+        continue;
+      };
+      self.nodes += 1;
+      let remaining = self.dfs(game_state, limit.saturating_sub(consumed.len()))?;
+      let undone = game_state.undo_move();
+      debug_assert!(undone.is_ok());
+
+      if consumed.len() + remaining > best.0 {
+        best = (consumed.len() + remaining, Some(dir));
+      }
+    }
+    self.memo.insert(key, best);
+    Some(best.0)
+  }
+
+  /// Follows the best moves stored in the memo.
+  fn memo_moves(&self, game_state: &GameState) -> Vec<(Direction, Amount)> {
+    let start = game_state.move_count();
+    let mut game_state = game_state.clone();
+    while let Some(&(_, Some(dir))) = self.memo.get(&Self::state_key(&game_state)) {
+      if game_state.move_(dir).is_err() {
+        break;
+      }
+    }
+    game_state.moves()[start..].to_vec()
+  }
+
+  /// Rates a state for the beam search. Consumed tiles matter most, mobility breaks ties.
+  fn rate(game_state: &GameState) -> usize {
+    let mobility = game_state
+      .rule_set()
+      .directions()
+      .filter(|&dir| game_state.check_move(dir).is_ok())
+      .count();
+    game_state.score() * Direction::ALL_DIRECTIONS_CW.len() + mobility
+  }
+
+  fn beam(&mut self, game_state: &GameState) -> GameState {
+    let mut best = game_state.clone();
+    let mut best_score = best.score();
+    let mut beam = alloc::vec![game_state.clone()];
+
+    while !beam.is_empty() {
+      let mut children = Vec::with_capacity(beam.len() * Direction::ALL_DIRECTIONS_CW.len());
+      'expand: for parent in &beam {
+        for dir in parent.rule_set().directions() {
+          if self.out_of_budget() {
+            break 'expand;
+          }
+          let mut child = parent.clone();
+          if child.move_(dir).is_err() {
+            continue;
+          }
+          self.nodes += 1;
+          let score = child.score();
+          if score > best_score {
+            best_score = score;
+            best = child.clone();
+          }
+          children.push((Self::rate(&child), child));
+        }
+      }
+
+      children.sort_by(|(a, _), (b, _)| b.cmp(a));
+      // Different move orders often lead to the same state
+      let mut seen = BTreeSet::new();
+      beam = children
+        .into_iter()
+        .map(|(_, child)| child)
+        .filter(|child| seen.insert(Self::state_key(child)))
+        .take(self.config.beam_width)
+        .collect();
+
+      if self.out_of_budget() {
+        break;
+      }
+    }
+    best
+  }
+}
+
+impl Solver {
+  #[must_use]
+  pub const fn new(config: SolverConfig) -> Self {
+    Self { config }
+  }
+
+  #[must_use]
+  pub const fn config(&self) -> &SolverConfig {
+    &self.config
+  }
+
+  #[must_use]
+  pub fn solve_game_field(&self, game_field: &GameField) -> Solution {
+    self.solve(&GameState::new(Arc::new(game_field.clone())))
+  }
+
+  /// Finds the best moves starting from `game_state`.
+  #[must_use]
+  pub fn solve(&self, game_state: &GameState) -> Solution {
+    let mut search = Search {
+      config: &self.config,
+      started: self.config.clock.now(),
+      time_budget: self.config.time_budget,
+      nodes: 0,
+      memo: BTreeMap::new(),
+    };
+
+    let max_score = Reachability::new(game_state).max_score();
+
+    if game_state.tile_count() <= self.config.exhaustive_tile_limit {
+      let mut working_state = game_state.clone();
+      let limit = max_score - game_state.score();
+      // Leave time for the beam search in case the exhaustive search doesn't finish
+      search.time_budget = self.config.time_budget.map(|time_budget| time_budget / 2);
+      if let Some(consumed) = search.dfs(&mut working_state, limit) {
+        return Solution {
+          moves: search.memo_moves(game_state),
+          score: game_state.score() + consumed,
+          proven_optimal: true,
+          nodes: search.nodes,
+        };
+      }
+    }
+    // Out of budget or too big. The beam search gets its own node budget and the rest of the time.
+    let dfs_nodes = search.nodes;
+    search.nodes = 0;
+    search.time_budget = self.config.time_budget;
+    search.memo = BTreeMap::new();
+
+    let best = search.beam(game_state);
+    let score = best.score();
+    Solution {
+      moves: best.moves()[game_state.move_count()..].to_vec(),
+      score,
+      // Nothing can beat consuming all reachable tiles
+      proven_optimal: score == max_score,
+      nodes: dfs_nodes + search.nodes,
+    }
+  }
+}
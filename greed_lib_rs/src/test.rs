@@ -1,5 +1,17 @@
 pub use super::*;
 
+use alloc::sync::Arc;
+
+/// Starts a game on the game field parsed from `field`.
+fn game_state(field: &str) -> GameState {
+  GameState::new(Arc::new(GameField::try_from(field).unwrap()))
+}
+
+/// A seeded game with undos, redo moves and move timestamps.
+fn golden_greed() -> Greed {
+  Greed::load_from_string(include_str!("test_data/played_game.json")).unwrap()
+}
+
 mod seed_test {
   use alloc::format;
 
@@ -8,7 +20,7 @@ mod seed_test {
   #[test]
   fn test_seed_to_many() {
     assert_eq!(
-      Seed::try_from("ABCD_abcd_1234#6x9#112233445566778899#1212312"),
+      Seed::try_from("ABCD_abcd_1234#6x9#112233445566778899#torus#uniform#1212312"),
       Err(SeedConversionError::UnexpectedHashTag)
     )
   }
@@ -131,6 +143,30 @@ mod seed_test {
       "ABCD_abcd_1234#6x9",
     )
   }
+
+  #[test]
+  fn test_random_seed_from_rng() {
+    use rand::SeedableRng;
+
+    let size = Size2D::new_unchecked(10, 7);
+    let session = |master_seed| {
+      let mut rng = rand_pcg::Pcg64Mcg::seed_from_u64(master_seed);
+      [
+        Seed::new_random_with_rng(size, None, &mut rng),
+        Seed::new_random_with_rng(size, None, &mut rng),
+      ]
+    };
+    let [first, second] = session(42);
+    assert_ne!(first, second);
+    assert_eq!(session(42), [first.clone(), second]);
+    assert_ne!(session(43)[0], first);
+    assert_eq!(first.size(), size);
+    assert!(UserString::try_from(first.user_str()).is_ok());
+
+    let mut rng = rand_pcg::Pcg64Mcg::seed_from_u64(42);
+    let greed = GreedBuilder::new().size(size).build_with_rng(&mut rng);
+    assert_eq!(greed.seed(), Some(&first));
+  }
 }
 
 mod game_field_test {
@@ -215,3 +251,2232 @@ mod greed_test {
     )
   }
 }
+
+mod validate_moves_test {
+  use alloc::{format, string::String};
+
+  use super::*;
+
+  const INITIAL_GAME_FIELD: &str = "{\"vec\":[0,1,1,1], \"size\":[4,1], \"player_pos\":[0,0]}";
+
+  fn load(moves: &str, last_game_field: Option<&str>) -> Greed {
+    let last_game_field = last_game_field.map_or_else(String::new, |last_game_field| {
+      format!(", \"last_game_field\":{last_game_field}")
+    });
+    Greed::load_from_string(&format!(
+      "{{\"initial_game_field\":{INITIAL_GAME_FIELD}, \"moves\":{moves}{last_game_field}}}"
+    ))
+    .unwrap()
+  }
+
+  #[test]
+  fn test_valid_moves() {
+    let greed = load("[[8,1],[8,1]]", None);
+    assert_eq!(greed.validate_moves(), Ok(()));
+  }
+
+  #[test]
+  fn test_valid_moves_with_last_game_field() {
+    let greed = load(
+      "[[8,1]]",
+      Some("{\"vec\":[0,0,1,1], \"size\":[4,1], \"player_pos\":[1,0]}"),
+    );
+    assert_eq!(greed.validate_moves(), Ok(()));
+  }
+
+  #[test]
+  fn test_invalid_move() {
+    let greed = load(
+      "[[8,1],[4,1]]",
+      Some("{\"vec\":[0,0,0,1], \"size\":[4,1], \"player_pos\":[2,0]}"),
+    );
+    assert_eq!(
+      greed.validate_moves(),
+      Err(MoveValidationError::InvalidMove { move_number: 1 })
+    );
+  }
+
+  #[test]
+  fn test_amount_mismatch() {
+    let greed = load(
+      "[[8,2]]",
+      Some("{\"vec\":[0,0,1,1], \"size\":[4,1], \"player_pos\":[1,0]}"),
+    );
+    assert_eq!(
+      greed.validate_moves(),
+      Err(MoveValidationError::AmountMismatch {
+        move_number: 0,
+        recorded: Amount::new_unchecked(2),
+        consumed: Amount::new_unchecked(1),
+      })
+    );
+  }
+
+  #[test]
+  fn test_invalid_move_without_last_game_field() {
+    let greed = load("[[8,1],[4,1],[8,1]]", None);
+    assert_eq!(greed.game_state().moves().len(), 3);
+    assert_eq!(
+      greed.validate_moves(),
+      Err(MoveValidationError::InvalidMove { move_number: 1 })
+    );
+  }
+
+  #[test]
+  fn test_amount_mismatch_without_last_game_field() {
+    let greed = load("[[8,9]]", None);
+    assert_eq!(
+      greed.game_state().moves(),
+      &[(Direction::RIGHT, Amount::new_unchecked(9))]
+    );
+    assert_eq!(
+      greed.validate_moves(),
+      Err(MoveValidationError::AmountMismatch {
+        move_number: 0,
+        recorded: Amount::new_unchecked(9),
+        consumed: Amount::new_unchecked(1),
+      })
+    );
+  }
+
+  #[test]
+  fn test_end_state_mismatch() {
+    let greed = load(
+      "[[8,1]]",
+      Some("{\"vec\":[0,0,0,1], \"size\":[4,1], \"player_pos\":[2,0]}"),
+    );
+    assert_eq!(
+      greed.validate_moves(),
+      Err(MoveValidationError::EndStateMismatch {
+        pos: Pos::new(1, 0)
+      })
+    );
+  }
+}
+
+mod validate_reproducibility_test {
+  use alloc::string::String;
+
+  use super::*;
+
+  fn seeded_greed() -> Greed {
+    GreedBuilder::new()
+      .seed(Seed::try_from("reproducible#8x5").unwrap())
+      .build()
+  }
+
+  #[test]
+  fn test_reproducible() {
+    let mut greed = seeded_greed();
+    let dir = Direction::ALL_DIRECTIONS_CW
+      .into_iter()
+      .find(|&dir| greed.check_move(dir).is_ok())
+      .unwrap();
+    greed.move_(dir).unwrap();
+    let greed = Greed::load_from_string(&greed.save_to_string()).unwrap();
+    assert_eq!(greed.validate_reproducibility(), Ok(()));
+  }
+
+  #[test]
+  fn test_missing_seed() {
+    let greed = Greed::load_from_string("@1\n").unwrap();
+    assert_eq!(
+      greed.validate_reproducibility(),
+      Err(ReproductionError::MissingSeed)
+    );
+  }
+
+  #[test]
+  fn test_wrong_tile() {
+    let greed = seeded_greed();
+    let mut game_meta = greed.game_meta();
+    let mut tampered = String::from(greed.game_field());
+    let (index, tile) = tampered
+      .char_indices()
+      .find(|&(_, c)| c.is_ascii_digit())
+      .unwrap();
+    let replacement = if tile == '9' { "1" } else { "9" };
+    tampered.replace_range(index..=index, replacement);
+    game_meta.initial_game_field = Some(GameField::try_from(tampered.as_str()).unwrap());
+    game_meta.last_game_field = None;
+    let tampered = Greed::load_from_string(&serde_json::to_string(&game_meta).unwrap()).unwrap();
+
+    // the string contains 9 chars per row (8 tiles and a newline)
+    #[allow(clippy::cast_possible_wrap)]
+    let pos = Pos::new((index % 9) as isize, (index / 9) as isize);
+    assert_eq!(
+      tampered.validate_reproducibility(),
+      Err(ReproductionError::WrongSeed {
+        pos,
+        seed_tile: Tile::try_from(tile).unwrap(),
+        game_field_tile: Tile::try_from(replacement.chars().next().unwrap()).unwrap(),
+      })
+    );
+  }
+
+  #[test]
+  fn test_wrong_size() {
+    let greed = seeded_greed();
+    let mut game_meta = greed.game_meta();
+    game_meta.seed = Some(Seed::try_from("reproducible#8x6").unwrap());
+    let tampered = Greed::load_from_string(&serde_json::to_string(&game_meta).unwrap()).unwrap();
+    assert_eq!(
+      tampered.validate_reproducibility(),
+      Err(ReproductionError::WrongSize {
+        seed_size: Size2D::new_unchecked(8, 6),
+        game_field_size: Size2D::new_unchecked(8, 5),
+      })
+    );
+  }
+}
+
+mod human_score_test {
+  use alloc::vec::Vec;
+
+  use super::*;
+
+  #[test]
+  fn test_human_score_breakdown() {
+    let timestamps: Vec<i64> = (1..=10).map(|move_number| move_number * 2000).collect();
+    assert_eq!(
+      HumanScore::new(50, 100, 8, 2, 60000, &timestamps),
+      HumanScore {
+        fill_permille: 500,
+        efficiency_permille: 800,
+        pace_permille: 1000,
+        average_move_time_ms: 2000,
+        human_score: 400,
+      }
+    );
+  }
+
+  #[test]
+  fn test_human_score_without_moves() {
+    assert_eq!(HumanScore::new(0, 100, 0, 0, 1000, &[]).human_score, 0);
+  }
+
+  #[test]
+  fn test_human_score_falls_back_to_time_spent() {
+    let human_score = HumanScore::new(100, 100, 10, 0, 0, &[]);
+    assert_eq!(human_score.pace_permille, 2000);
+    assert_eq!(human_score.human_score, 2000);
+  }
+
+  #[test]
+  fn test_move_timestamps_are_saved() {
+    let mut greed = GreedBuilder::new()
+      .seed(Seed::try_from("timestamps#8x5").unwrap())
+      .build();
+    let dir = Direction::ALL_DIRECTIONS_CW
+      .into_iter()
+      .find(|&dir| greed.check_move(dir).is_ok())
+      .unwrap();
+    greed.move_(dir).unwrap();
+    greed.undo_move().unwrap();
+    greed.move_(dir).unwrap();
+    assert_eq!(greed.move_timestamps_ms().len(), 3);
+
+    let loaded = Greed::load_from_string(&greed.save_to_string()).unwrap();
+    assert_eq!(loaded.move_timestamps_ms(), greed.move_timestamps_ms());
+    assert_eq!(loaded.total_move_count(), 2);
+  }
+}
+
+mod checked_move_test {
+  use alloc::vec::Vec;
+
+  use super::*;
+
+  #[test]
+  fn test_commit_checked_move() {
+    let mut game_state = game_state("@1112\n");
+    let mut expected = game_state.clone();
+    let checked_move = game_state.checked_move(Direction::RIGHT).unwrap();
+    assert_eq!(checked_move.indices(), &[1]);
+    assert_eq!(
+      game_state.commit_move(checked_move),
+      expected.move_(Direction::RIGHT)
+    );
+    assert_eq!(game_state, expected);
+  }
+
+  #[test]
+  fn test_checked_move_errors_like_check_move() {
+    let game_state = game_state("@1112\n");
+    assert_eq!(
+      game_state.checked_move(Direction::LEFT),
+      Err(PlayableError::BadMove)
+    );
+  }
+
+  #[test]
+  fn test_stale_checked_move() {
+    let mut game_state = game_state("@1112\n");
+    let checked_move = game_state.checked_move(Direction::RIGHT).unwrap();
+    game_state.move_(Direction::RIGHT).unwrap();
+    assert_eq!(
+      game_state.commit_move(checked_move),
+      Err(PlayableError::StaleCheckedMove)
+    );
+  }
+
+  #[test]
+  fn test_checked_move_from_other_state() {
+    let checked_move = game_state("@1112\n")
+      .checked_move(Direction::RIGHT)
+      .unwrap();
+    let mut game_state = game_state("@1112\n");
+    assert_eq!(
+      game_state.commit_move(checked_move),
+      Err(PlayableError::StaleCheckedMove)
+    );
+  }
+
+  #[test]
+  fn test_checked_move_after_undo() {
+    let mut game_state = game_state("@1112\n");
+    let checked_move = game_state.checked_move(Direction::RIGHT).unwrap();
+    game_state.move_(Direction::RIGHT).unwrap();
+    game_state.undo_move().unwrap();
+    assert!(game_state.commit_move(checked_move).is_ok());
+  }
+
+  /// Only implements the required methods of `Playable`.
+  struct MinimalPlayable(GameState);
+
+  impl Playable for MinimalPlayable {
+    fn check_move(&self, dir: Direction) -> Result<Vec<usize>, PlayableError> {
+      self.0.check_move(dir)
+    }
+    fn move_(&mut self, dir: Direction) -> Result<Vec<usize>, PlayableError> {
+      self.0.move_(dir)
+    }
+    fn undo_move(&mut self) -> Result<(), PlayableError> {
+      self.0.undo_move()
+    }
+    fn game_field(&self) -> &GameField {
+      self.0.game_field()
+    }
+    fn move_count(&self) -> usize {
+      self.0.move_count()
+    }
+  }
+
+  #[test]
+  fn test_default_checked_move() {
+    let mut playable = MinimalPlayable(game_state("@1112\n"));
+    let checked_move = playable.checked_move(Direction::RIGHT).unwrap();
+    let stale = checked_move.clone();
+    assert_eq!(playable.commit_move(checked_move), Ok(alloc::vec![1]));
+    assert_eq!(
+      playable.commit_move(stale),
+      Err(PlayableError::StaleCheckedMove)
+    );
+    assert_eq!(playable.redo_move(), Err(PlayableError::BadMove));
+
+    // Moves checked by the default implementation aren't bound to a game state
+    let checked_move = playable.checked_move(Direction::RIGHT).unwrap();
+    assert_eq!(
+      game_state("@1112\n").commit_move(checked_move),
+      Err(PlayableError::StaleCheckedMove)
+    );
+  }
+}
+
+mod redo_test {
+  use super::*;
+
+  #[test]
+  fn test_undo_redo() {
+    let mut game_state = game_state("@1112\n");
+    game_state.move_(Direction::RIGHT).unwrap();
+    game_state.move_(Direction::RIGHT).unwrap();
+    let expected = game_state.clone();
+
+    game_state.undo_move().unwrap();
+    game_state.undo_move().unwrap();
+    assert_eq!(game_state.redo_moves().len(), 2);
+    assert_eq!(game_state.redo_move(), Ok(alloc::vec![1]));
+    assert_eq!(game_state.redo_move(), Ok(alloc::vec![2]));
+    assert_eq!(game_state.redo_move(), Err(PlayableError::BadMove));
+    assert_eq!(game_state, expected);
+  }
+
+  #[test]
+  fn test_same_move_keeps_redo_moves() {
+    let mut game_state = game_state("@1112\n");
+    game_state.move_(Direction::RIGHT).unwrap();
+    game_state.move_(Direction::RIGHT).unwrap();
+    game_state.undo_move().unwrap();
+    game_state.undo_move().unwrap();
+    game_state.move_(Direction::RIGHT).unwrap();
+    assert_eq!(game_state.redo_moves().len(), 1);
+  }
+
+  #[test]
+  fn test_diverging_move_clears_redo_moves() {
+    let mut game_state = game_state("@1\n11\n");
+    game_state.move_(Direction::RIGHT).unwrap();
+    game_state.undo_move().unwrap();
+    game_state.move_(Direction::DOWN).unwrap();
+    assert_eq!(game_state.redo_moves(), &[]);
+  }
+
+  #[test]
+  fn test_redo_moves_are_saved() {
+    let mut greed = Greed::load_from_string("@1112\n").unwrap();
+    for _ in 0..3 {
+      greed.move_(Direction::RIGHT).unwrap();
+    }
+    greed.undo_move().unwrap();
+    greed.undo_move().unwrap();
+
+    let mut loaded = Greed::load_from_string(&greed.save_to_string()).unwrap();
+    assert_eq!(
+      loaded.game_state().redo_moves(),
+      greed.game_state().redo_moves()
+    );
+    loaded.redo_move().unwrap();
+    loaded.redo_move().unwrap();
+    assert_eq!(loaded.move_count(), 3);
+    assert_eq!(loaded.validate_moves(), Ok(()));
+  }
+
+  #[test]
+  fn test_redo_invalid_move() {
+    let mut greed = Greed::load_from_string(
+      "{\"initial_game_field\":{\"vec\":[0,1,1,1], \"size\":[4,1], \"player_pos\":[0,0]}, \"redo_moves\":[[4,1]]}",
+    )
+    .unwrap();
+    assert_eq!(greed.redo_move(), Err(PlayableError::RedoInvalidMove));
+  }
+}
+
+mod solver_test {
+  use alloc::sync::Arc;
+
+  use chrono::{DateTime, Duration, Utc};
+
+  use super::*;
+
+  fn replay(game_field: &GameField, moves: &[(Direction, Amount)]) -> GameState {
+    let mut game_state = GameState::new(Arc::new(game_field.clone()));
+    for &(dir, amount) in moves {
+      let consumed = game_state.move_(dir).unwrap();
+      assert_eq!(consumed.len(), usize::from(amount.amount()));
+    }
+    game_state
+  }
+
+  #[test]
+  fn test_solve_line() {
+    let game_field = GameField::try_from("@1112\n").unwrap();
+    let solution = Solver::default().solve_game_field(&game_field);
+    assert!(solution.proven_optimal);
+    assert_eq!(solution.score, 3);
+    assert_eq!(
+      solution.moves,
+      [(Direction::RIGHT, Amount::new_unchecked(1)); 3]
+    );
+  }
+
+  #[test]
+  fn test_solve_small_board() {
+    let game_field = GameField::from_seed(&Seed::try_from("solver#5x4").unwrap());
+    let solution = Solver::default().solve_game_field(&game_field);
+    assert!(solution.proven_optimal);
+    assert_eq!(replay(&game_field, &solution.moves).score(), solution.score);
+  }
+
+  #[test]
+  fn test_solve_from_game_state() {
+    let game_field = Arc::new(GameField::try_from("@1112\n").unwrap());
+    let mut game_state = GameState::new(game_field);
+    game_state.move_(Direction::RIGHT).unwrap();
+    let solution = Solver::default().solve(&game_state);
+    assert_eq!(solution.score, 3);
+    assert_eq!(solution.moves.len(), 2);
+  }
+
+  #[test]
+  fn test_beam_search_default_size() {
+    let game_field = GameField::from_seed(&Seed::try_from("solver").unwrap());
+    let solver = Solver::new(SolverConfig {
+      node_budget: 2000,
+      beam_width: 8,
+      ..Default::default()
+    });
+    let solution = solver.solve_game_field(&game_field);
+    assert_ne!(solution.moves, []);
+    assert!(solution.nodes <= 2000);
+    assert_eq!(replay(&game_field, &solution.moves).score(), solution.score);
+  }
+
+  #[test]
+  fn test_out_of_budget_is_not_proven() {
+    let game_field = GameField::from_seed(&Seed::try_from("solver#8x8").unwrap());
+    let solver = Solver::new(SolverConfig {
+      node_budget: 10,
+      ..Default::default()
+    });
+    let solution = solver.solve_game_field(&game_field);
+    assert!(!solution.proven_optimal);
+    assert_eq!(replay(&game_field, &solution.moves).score(), solution.score);
+  }
+
+  /// Every read of the time takes a second.
+  struct TickingClock(ManualClock);
+
+  impl Clock for TickingClock {
+    fn now(&self) -> DateTime<Utc> {
+      self.0.advance(Duration::seconds(1));
+      self.0.now()
+    }
+  }
+
+  #[test]
+  fn test_beam_search_after_time_budget() {
+    let game_field = GameField::try_from("@1111111\n11111111\n").unwrap();
+    let solver = Solver::new(SolverConfig {
+      time_budget: Some(Duration::milliseconds(1)),
+      clock: SharedClock::new(TickingClock(ManualClock::default())),
+      ..Default::default()
+    });
+    let solution = solver.solve_game_field(&game_field);
+    assert_ne!(solution.moves, []);
+    assert!(solution.score > 0);
+    assert_eq!(replay(&game_field, &solution.moves).score(), solution.score);
+  }
+}
+
+mod hint_test {
+  use super::*;
+
+  #[test]
+  fn test_hints_are_ranked() {
+    // Going right consumes 3 tiles, going down only 1 and leads into a dead end
+    let game_state = game_state("@311\n1999\n");
+    let hints = hints(&game_state, &HintConfig::default());
+    assert_eq!(hints.len(), 2);
+    assert_eq!(hints[0].dir, Direction::RIGHT);
+    assert_eq!(hints[0].amount, Amount::new_unchecked(3));
+    assert!(hints[0].rating > hints[1].rating);
+  }
+
+  #[test]
+  fn test_no_hints_when_complete() {
+    let game_state = game_state("@2\n");
+    assert_eq!(hints(&game_state, &HintConfig::default()), []);
+  }
+
+  #[test]
+  fn test_hints_do_not_modify_greed() {
+    let greed = Greed::load_from_string("@1112\n").unwrap();
+    let config = HintConfig {
+      depth: 4,
+      ..Default::default()
+    };
+    let hints = greed.hints(&config);
+    assert_eq!(hints[0].dir, Direction::RIGHT);
+    assert_eq!(greed.move_count(), 0);
+    assert_eq!(greed.game_state().redo_moves(), &[]);
+  }
+}
+
+mod reachability_test {
+  use alloc::sync::Arc;
+
+  use super::*;
+
+  #[test]
+  fn test_line() {
+    let reachability = Reachability::new(&game_state("@1112\n"));
+    assert_eq!(reachability.reachable_count(), 3);
+    assert_eq!(reachability.dead_count(), 1);
+    assert_eq!(reachability.max_score(), 3);
+    assert!(reachability.is_reachable(3));
+    assert!(!reachability.is_reachable(4));
+  }
+
+  #[test]
+  fn test_dead_tiles() {
+    let reachability = Reachability::new(&game_state("@1\n99\n"));
+    assert_eq!(reachability.reachable_count(), 1);
+    assert_eq!(reachability.dead_count(), 2);
+  }
+
+  #[test]
+  fn test_after_moves() {
+    let mut game_state = game_state("@1112\n");
+    game_state.move_(Direction::RIGHT).unwrap();
+    let reachability = Reachability::new(&game_state);
+    assert_eq!(reachability.reachable_count(), 2);
+    assert_eq!(reachability.max_score(), 3);
+  }
+
+  #[test]
+  fn test_upper_bound() {
+    for user_str in ["bound_a", "bound_b", "bound_c"] {
+      let seed = Seed::new(
+        UserString::try_from(user_str).unwrap(),
+        Size2D::new_unchecked(5, 4),
+        None,
+      );
+      let game_state = GameState::new(Arc::new(GameField::from_seed(&seed)));
+      let solution = Solver::default().solve(&game_state);
+      assert!(solution.proven_optimal);
+      assert!(solution.score <= Reachability::new(&game_state).max_score());
+    }
+  }
+}
+
+mod difficulty_test {
+  use super::*;
+
+  fn config() -> DifficultyConfig {
+    DifficultyConfig {
+      playouts: 16,
+      ..Default::default()
+    }
+  }
+
+  #[test]
+  fn test_estimate_seed_is_reproducible() {
+    let seed = Seed::try_from("difficulty#6x4").unwrap();
+    assert_eq!(
+      Difficulty::estimate_seed(&seed, &config()),
+      Difficulty::estimate_seed(&seed, &config())
+    );
+  }
+
+  #[test]
+  fn test_estimate_statistics() {
+    let seed = Seed::try_from("difficulty#6x4").unwrap();
+    let difficulty = Difficulty::estimate_seed(&seed, &config());
+    assert!(difficulty.worst_playout_score <= difficulty.average_playout_score);
+    assert!(difficulty.average_playout_score <= difficulty.best_playout_score);
+    assert!(difficulty.best_playout_score <= difficulty.solver_score);
+    assert!(difficulty.solver_proven_optimal);
+    assert_eq!(difficulty.map.dimensions(), seed.size());
+    assert_eq!(difficulty.map.playouts(), 16);
+    let player_pos = GameField::from_seed(&seed).player_pos();
+    assert_eq!(difficulty.map.consumed_at(player_pos), Some(0));
+  }
+
+  #[test]
+  fn test_trivial_board_is_easy() {
+    let game_field = GameField::try_from("@1111\n").unwrap();
+    let mut rng = rand_pcg::Pcg64Mcg::new(0);
+    let difficulty = Difficulty::estimate(&game_field, &config(), &mut rng);
+    assert_eq!(difficulty.average_playout_score, 4);
+    assert_eq!(difficulty.initial_branching, 1);
+    assert_eq!(difficulty.average_branching_permille, 1000);
+    assert_eq!(difficulty.rating, DifficultyRating::Easy);
+    assert_eq!(difficulty.map.consumed_permille(4), Some(1000));
+  }
+
+  #[test]
+  fn test_rating_thresholds() {
+    assert_eq!(
+      DifficultyRating::from_playout_permille(500),
+      DifficultyRating::Easy
+    );
+    assert_eq!(
+      DifficultyRating::from_playout_permille(499),
+      DifficultyRating::Medium
+    );
+    assert_eq!(
+      DifficultyRating::from_playout_permille(249),
+      DifficultyRating::Hard
+    );
+  }
+}
+
+#[cfg(feature = "std")]
+mod reader_writer_test {
+  use alloc::{sync::Arc, vec::Vec};
+  use std::io;
+
+  use chrono::Duration;
+
+  use super::*;
+
+  struct FailingIo;
+
+  impl io::Read for FailingIo {
+    fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+      Err(io::ErrorKind::BrokenPipe.into())
+    }
+  }
+
+  impl io::Write for FailingIo {
+    fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+      Err(io::ErrorKind::BrokenPipe.into())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+      Ok(())
+    }
+  }
+
+  #[test]
+  fn test_round_trip() {
+    let mut greed = Greed::load_from_string("@1112\n").unwrap();
+    greed.move_(Direction::RIGHT).unwrap();
+    let mut buffer = Vec::new();
+    greed.save_to_writer(&mut buffer).unwrap();
+    assert_eq!(buffer, greed.save_to_string().as_bytes());
+    let loaded = Greed::load_from_reader(buffer.as_slice()).unwrap();
+    assert_eq!(loaded.game_state(), greed.game_state());
+  }
+
+  #[test]
+  fn test_load_game_field() {
+    let greed = Greed::load_from_reader("@1112\n".as_bytes()).unwrap();
+    assert_eq!(
+      greed.game_state(),
+      Greed::load_from_string("@1112\n").unwrap().game_state()
+    );
+  }
+
+  #[test]
+  fn test_load_golden_save() {
+    let save = include_str!("test_data/save_v0.json");
+    let greed = Greed::load_from_reader(save.as_bytes()).unwrap();
+    assert_eq!(
+      greed.game_state(),
+      Greed::load_from_string(save).unwrap().game_state()
+    );
+  }
+
+  #[test]
+  fn test_load_with_clock() {
+    let clock = Arc::new(ManualClock::default());
+    let greed =
+      Greed::load_from_reader_with_clock("@1112\n".as_bytes(), SharedClock::from(clock.clone()))
+        .unwrap();
+    clock.advance(Duration::seconds(5));
+    assert_eq!(greed.time_spent(), Duration::seconds(5));
+  }
+
+  #[test]
+  fn test_load_binary() {
+    let greed = Greed::load_from_string(include_str!("test_data/played_game.json")).unwrap();
+    let bytes = greed.save_to_bytes().unwrap();
+    let loaded = Greed::load_from_reader(bytes.as_slice()).unwrap();
+    assert_eq!(loaded.game_state(), greed.game_state());
+  }
+
+  #[test]
+  fn test_empty_reader() {
+    assert_eq!(
+      Greed::load_from_reader(io::empty()),
+      Err(GreedParserError::EmptyString)
+    );
+  }
+
+  #[test]
+  fn test_invalid_json() {
+    assert!(matches!(
+      Greed::load_from_reader("{?}".as_bytes()),
+      Err(GreedParserError::InvalidMetaDataFromat { .. })
+    ));
+  }
+
+  #[test]
+  fn test_io_errors() {
+    let Err(GreedParserError::Io { source }) = Greed::load_from_reader(FailingIo) else {
+      panic!("expected an io error");
+    };
+    assert_eq!(source.kind(), io::ErrorKind::BrokenPipe);
+
+    let greed = Greed::load_from_string("@1112\n").unwrap();
+    assert!(matches!(
+      greed.save_to_writer(FailingIo),
+      Err(GreedParserError::Io { .. })
+    ));
+  }
+}
+
+mod save_migration_test {
+  use alloc::{format, string::String};
+
+  use super::*;
+
+  /// One save written by every `file_version`, 0 is a save without `file_version`.
+  /// Never edit these, add a new one instead.
+  const GOLDEN_SAVES: [(u64, &str); 2] = [
+    (0, include_str!("test_data/save_v0.json")),
+    (1, include_str!("test_data/save_v1.json")),
+  ];
+
+  #[test]
+  fn test_every_version_has_golden_save() {
+    for (index, (version, _)) in GOLDEN_SAVES.into_iter().enumerate() {
+      assert_eq!(version, index as u64);
+    }
+    assert_eq!(GOLDEN_SAVES.len() as u64, CURRENT_FILE_VERSION + 1);
+  }
+
+  #[test]
+  fn test_golden_saves_load() {
+    for (version, save) in GOLDEN_SAVES {
+      let greed = Greed::load_from_string(save)
+        .unwrap_or_else(|err| panic!("version {version} failed to load: {err}"));
+      assert_eq!(greed.name(), "golden");
+      assert_eq!(greed.score(), 4);
+      assert_eq!(greed.move_count(), 3);
+      assert_eq!(greed.validate_reproducibility(), Ok(()));
+      assert_eq!(greed.game_meta().file_version, Some(CURRENT_FILE_VERSION));
+    }
+  }
+
+  #[test]
+  fn test_golden_saves_migrate_to_same_state() {
+    let current = Greed::load_from_string(GOLDEN_SAVES[GOLDEN_SAVES.len() - 1].1).unwrap();
+    for (_, save) in GOLDEN_SAVES {
+      let greed = Greed::load_from_string(save).unwrap();
+      assert_eq!(
+        greed.game_state().to_game_field(),
+        current.game_state().to_game_field()
+      );
+      assert_eq!(greed.game_state().moves(), current.game_state().moves());
+    }
+  }
+
+  #[test]
+  fn test_future_version() {
+    let future_version = CURRENT_FILE_VERSION + 1;
+    assert_eq!(
+      Greed::load_from_string(&format!(
+        "{{\"file_version\":{future_version},\"seed\":\"e\"}}"
+      )),
+      Err(GreedParserError::UnsupportedFileVersion {
+        found: future_version,
+        supported: CURRENT_FILE_VERSION
+      })
+    );
+  }
+
+  #[test]
+  fn test_invalid_version() {
+    assert!(matches!(
+      Greed::load_from_string("{\"file_version\":\"one\",\"seed\":\"e\"}"),
+      Err(GreedParserError::InvalidMetaDataFromat { .. })
+    ));
+  }
+
+  #[test]
+  fn test_unversioned() {
+    assert!(Greed::load_from_string("{\"file_version\":null,\"seed\":\"e\"}").is_ok());
+    assert!(Greed::load_from_string("{\"seed\":\"e\"}").is_ok());
+  }
+
+  #[test]
+  fn test_migration_steps() {
+    fn rename_title(game_meta: &mut serde_json::Map<String, serde_json::Value>) {
+      if let Some(title) = game_meta.remove("title") {
+        game_meta.insert("name".into(), title);
+      }
+    }
+    let value = serde_json::json!({"file_version": 1, "title": "old", "seed": "e"});
+
+    let game_meta = save_migration::migrate_value(value.clone(), 1, &[rename_title]).unwrap();
+    assert_eq!(game_meta.name.as_deref(), Some("old"));
+    assert_eq!(game_meta.file_version, Some(CURRENT_FILE_VERSION));
+
+    // Steps for older versions are skipped
+    let game_meta = save_migration::migrate_value(value, 2, &[rename_title]).unwrap();
+    assert_eq!(game_meta.name, None);
+  }
+}
+
+mod binary_save_test {
+  use alloc::vec::Vec;
+
+  use super::*;
+
+  #[test]
+  fn test_round_trip_seed() {
+    let greed = golden_greed();
+    let bytes = greed.save_to_bytes().unwrap();
+    // The board is generated from the seed instead of being stored
+    assert_eq!(
+      GameMeta::from_bytes(&bytes).unwrap().initial_game_field,
+      None
+    );
+    assert!(bytes.len() * 4 < greed.save_to_string().len());
+    let loaded = Greed::load_from_bytes(&bytes).unwrap();
+    assert_eq!(loaded.game_state(), greed.game_state());
+    assert_eq!(loaded.seed(), greed.seed());
+    assert_eq!(loaded.name(), greed.name());
+    assert_eq!(loaded.undo_count(), greed.undo_count());
+    assert_eq!(loaded.move_timestamps_ms(), greed.move_timestamps_ms());
+    assert_eq!(loaded.validate_reproducibility(), Ok(()));
+  }
+
+  #[test]
+  fn test_round_trip_game_field() {
+    let mut greed = Greed::load_from_string("@1112\n").unwrap();
+    greed.move_(Direction::RIGHT).unwrap();
+    let loaded = Greed::load_from_bytes(&greed.save_to_bytes().unwrap()).unwrap();
+    assert_eq!(loaded.game_state(), greed.game_state());
+    assert_eq!(loaded.game_field(), greed.game_field());
+  }
+
+  #[test]
+  fn test_round_trip_game_meta() {
+    let game_meta = golden_greed().game_meta();
+    let decoded = GameMeta::from_bytes(&game_meta.to_bytes().unwrap()).unwrap();
+    assert_eq!(
+      decoded,
+      GameMeta {
+        score: None,
+        human_score: None,
+        initial_game_field: None,
+        last_game_field: None,
+        ..game_meta
+      }
+    );
+  }
+
+  #[test]
+  fn test_round_trip_last_game_field() {
+    let mut greed = Greed::load_from_string("@1112\n").unwrap();
+    greed.move_(Direction::RIGHT).unwrap();
+    let game_meta = greed.game_meta();
+    let only_last = GameMeta {
+      initial_game_field: None,
+      ..game_meta.clone()
+    };
+    // The moves would be replayed on the already played board
+    assert_eq!(
+      only_last.to_bytes(),
+      Err(BinaryFormatError::MissingInitialGameField)
+    );
+
+    let decoded = GameMeta::from_bytes(&game_meta.to_bytes().unwrap()).unwrap();
+    assert_eq!(decoded.initial_game_field, game_meta.initial_game_field);
+    let loaded = Greed::load_from_game_meta(decoded).unwrap();
+    assert_eq!(loaded.game_state(), greed.game_state());
+
+    // With a seed the board is generated instead
+    let greed = golden_greed();
+    let seeded = GameMeta {
+      initial_game_field: None,
+      ..greed.game_meta()
+    };
+    let loaded =
+      Greed::load_from_game_meta(GameMeta::from_bytes(&seeded.to_bytes().unwrap()).unwrap())
+        .unwrap();
+    assert_eq!(loaded.game_state(), greed.game_state());
+  }
+
+  #[test]
+  fn test_odd_tile_count() {
+    let game_meta = GameMeta {
+      initial_game_field: Some(GameField::try_from("987\n65@\n321\n").unwrap()),
+      moves: Some(alloc::vec![(Direction::UP, Amount::try_from(1u8).unwrap())]),
+      ..Default::default()
+    };
+    let decoded = GameMeta::from_bytes(&game_meta.to_bytes().unwrap()).unwrap();
+    assert_eq!(decoded.initial_game_field, game_meta.initial_game_field);
+    assert_eq!(decoded.moves, game_meta.moves);
+  }
+
+  #[test]
+  fn test_invalid_redo_direction() {
+    // Redo moves are only checked once they are redone
+    let greed = Greed::load_from_string("{\"seed\":\"e\",\"redo_moves\":[[3,1]]}").unwrap();
+    assert_eq!(
+      greed.save_to_bytes(),
+      Err(BinaryFormatError::InvalidDirection)
+    );
+  }
+
+  #[test]
+  fn test_detects_json() {
+    let greed = golden_greed();
+    let loaded = Greed::load_from_bytes(greed.save_to_string().as_bytes()).unwrap();
+    assert_eq!(loaded.game_state(), greed.game_state());
+  }
+
+  #[test]
+  fn test_invalid_bytes() {
+    let bytes = golden_greed().save_to_bytes().unwrap();
+    assert_eq!(
+      Greed::load_from_bytes(&bytes[..bytes.len() - 1]),
+      Err(BinaryFormatError::UnexpectedEnd.into())
+    );
+
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert_eq!(
+      Greed::load_from_bytes(&trailing),
+      Err(BinaryFormatError::TrailingBytes { count: 1 }.into())
+    );
+
+    let mut future: Vec<u8> = bytes.clone();
+    future[BINARY_MAGIC.len()] = u8::MAX;
+    assert!(matches!(
+      Greed::load_from_bytes(&future),
+      Err(GreedParserError::InvalidBinaryFormat {
+        source: BinaryFormatError::UnsupportedVersion { found: u8::MAX, .. }
+      })
+    ));
+
+    assert_eq!(
+      Greed::load_from_bytes(&[0xFF]),
+      Err(BinaryFormatError::InvalidUtf8.into())
+    );
+  }
+}
+
+mod move_notation_test {
+  use alloc::{string::String, vec::Vec};
+
+  use super::*;
+
+  fn moves(moves: &[(Direction, u8)]) -> Vec<(Direction, Amount)> {
+    moves
+      .iter()
+      .map(|&(dir, amount)| (dir, Amount::try_from(amount).unwrap()))
+      .collect()
+  }
+
+  #[test]
+  fn test_direction_round_trip() {
+    for dir in Direction::ALL_DIRECTIONS_CW {
+      let digit = dir.numpad_digit().unwrap();
+      assert_eq!(Direction::from_numpad_digit(digit), Some(dir));
+      let name = dir.compass_name().unwrap();
+      assert_eq!(Direction::from_compass_name(name), Some(dir));
+    }
+    assert_eq!(Direction::UP.numpad_digit(), Some('8'));
+    assert_eq!(
+      Direction::DOWN.union(Direction::RIGHT).compass_name(),
+      Some("SE")
+    );
+    assert_eq!(
+      Direction::from_compass_name("nw"),
+      Some(Direction::UP | Direction::LEFT)
+    );
+    assert_eq!(Direction::from_numpad_digit('5'), None);
+    assert_eq!((Direction::UP | Direction::DOWN).numpad_digit(), None);
+  }
+
+  #[test]
+  fn test_format_directions() {
+    let dirs = [
+      Direction::UP,
+      Direction::UP | Direction::RIGHT,
+      Direction::RIGHT,
+    ];
+    assert_eq!(
+      MoveNotation::Numpad.format_directions(&dirs),
+      Ok(String::from("896"))
+    );
+    assert_eq!(
+      MoveNotation::Compass.format_directions(&dirs),
+      Ok(String::from("N NE E"))
+    );
+    assert_eq!(
+      MoveNotation::Numpad.format_directions(&[Direction::empty()]),
+      Err(MoveNotationError::InvalidDirection {
+        found: alloc::format!("{:?}", Direction::empty())
+      })
+    );
+  }
+
+  #[test]
+  fn test_parse_directions() {
+    let dirs = alloc::vec![
+      Direction::UP,
+      Direction::UP | Direction::RIGHT,
+      Direction::RIGHT
+    ];
+    assert_eq!(parse_directions("896"), Ok(dirs.clone()));
+    assert_eq!(parse_directions("N NE E"), Ok(dirs.clone()));
+    assert_eq!(parse_directions(" 8, ne 6\n"), Ok(dirs));
+    assert_eq!(
+      parse_directions("85"),
+      Err(MoveNotationError::InvalidDirection { found: "5".into() })
+    );
+    assert_eq!(parse_directions(""), Ok(Vec::new()));
+  }
+
+  #[test]
+  fn test_moves_round_trip() {
+    let moves = moves(&[(Direction::UP, 3), (Direction::DOWN | Direction::LEFT, 1)]);
+    let numpad = MoveNotation::Numpad.format_moves(&moves).unwrap();
+    assert_eq!(numpad, "83 11");
+    assert_eq!(parse_moves(&numpad), Ok(moves.clone()));
+    let compass = MoveNotation::Compass.format_moves(&moves).unwrap();
+    assert_eq!(compass, "N3 SW1");
+    assert_eq!(parse_moves(&compass), Ok(moves));
+    assert_eq!(
+      parse_moves("N"),
+      Err(MoveNotationError::InvalidMove { found: "N".into() })
+    );
+  }
+
+  #[test]
+  fn test_replay_pasted_solution() {
+    let mut game_state = Greed::load_from_string("@1112\n")
+      .unwrap()
+      .game_state()
+      .clone();
+    for dir in parse_directions("6 6").unwrap() {
+      game_state.move_(dir).unwrap();
+    }
+    assert_eq!(game_state.score(), 2);
+  }
+
+  #[test]
+  fn test_game_meta_accepts_notation() {
+    let greed = Greed::load_from_string(
+      "{\"initial_game_field\":{\"vec\":[0,1,1,1,2],\"size\":[5,1],\"player_pos\":[0,0]},\
+       \"moves\":\"E1 6 1\"}",
+    );
+    // Every move needs an amount
+    assert!(matches!(
+      greed,
+      Err(GreedParserError::InvalidMetaDataFromat { .. })
+    ));
+    let greed = Greed::load_from_string(
+      "{\"initial_game_field\":{\"vec\":[0,1,1,1,2],\"size\":[5,1],\"player_pos\":[0,0]},\
+       \"moves\":\"E1 61\"}",
+    )
+    .unwrap();
+    assert_eq!(greed.move_count(), 2);
+    // Saving keeps the array format
+    let saved = Greed::load_from_string(&greed.save_to_string()).unwrap();
+    assert_eq!(saved.game_state(), greed.game_state());
+  }
+
+  #[derive(serde::Serialize, serde::Deserialize)]
+  struct Replay {
+    #[serde(with = "serde_with::As::<MoveList>")]
+    moves: Vec<(Direction, Amount)>,
+  }
+
+  #[test]
+  fn test_move_list_adapter() {
+    let replay = Replay {
+      moves: moves(&[(Direction::RIGHT, 2), (Direction::UP, 1)]),
+    };
+    let json = serde_json::to_string(&replay).unwrap();
+    assert_eq!(json, "{\"moves\":\"E2 N1\"}");
+    let parsed: Replay = serde_json::from_str("{\"moves\":[[8,2],[1,1]]}").unwrap();
+    assert_eq!(parsed.moves, replay.moves);
+  }
+}
+
+mod share_code_test {
+  use alloc::{string::ToString, vec::Vec};
+
+  use super::*;
+
+  #[test]
+  fn test_round_trip() {
+    let greed = golden_greed();
+    let share_code = ShareCode::from_greed(&greed).unwrap();
+    let code = share_code.to_string();
+    assert!(code.starts_with("archive-8x5-"));
+    assert!(code
+      .chars()
+      .all(|char| char.is_ascii_alphanumeric() || "_-.".contains(char)));
+    assert_eq!(ShareCode::try_from(code.as_str()), Ok(share_code.clone()));
+    assert_eq!(share_code.target_score(), Some(greed.score()));
+
+    let built = share_code.build_greed().unwrap();
+    assert_eq!(built.game_state().moves(), greed.game_state().moves());
+    assert_eq!(built.score(), greed.score());
+  }
+
+  #[test]
+  fn test_optional_parts() {
+    let seed = Seed::try_from("abc#4fx15").unwrap();
+    let share_code = ShareCode::new(seed.clone(), Vec::new(), None).unwrap();
+    let code = share_code.to_string();
+    assert!(code.starts_with("abc-4fx15..."));
+    let parsed = ShareCode::try_from(code.as_str()).unwrap();
+    assert_eq!(parsed.seed(), &seed);
+    assert_eq!(parsed.moves(), &[]);
+    assert_eq!(parsed.target_score(), None);
+  }
+
+  #[test]
+  fn test_checksum() {
+    let code = ShareCode::from_greed(&golden_greed()).unwrap().to_string();
+    let typo = code.replacen("archive", "archivf", 1);
+    assert_eq!(
+      ShareCode::try_from(typo.as_str()),
+      Err(ShareCodeError::ChecksumMismatch)
+    );
+    // The checksum is case insensitive
+    let (body, checksum) = code.rsplit_once('.').unwrap();
+    let upper_checksum = alloc::format!("{body}.{}", checksum.to_uppercase());
+    assert_eq!(
+      ShareCode::try_from(upper_checksum.as_str()),
+      ShareCode::try_from(code.as_str())
+    );
+  }
+
+  #[test]
+  fn test_invalid() {
+    assert_eq!(
+      ShareCode::try_from("abc.8"),
+      Err(ShareCodeError::WrongPartCount { found: 2 })
+    );
+    assert_eq!(
+      ShareCode::new(
+        Seed::try_from("abc").unwrap(),
+        alloc::vec![Direction::empty()],
+        None
+      ),
+      Err(ShareCodeError::InvalidMoves {
+        source: MoveNotationError::InvalidDirection {
+          found: alloc::format!("{:?}", Direction::empty())
+        }
+      })
+    );
+  }
+
+  #[test]
+  fn test_custom_rule_set() {
+    let mut builder = GreedBuilder::new().seed(Seed::try_from("abc").unwrap());
+    builder.rule_set(RuleSet::ORTHOGONAL);
+    assert_eq!(ShareCode::from_greed(&builder.build()), None);
+  }
+
+  #[test]
+  fn test_impossible_moves() {
+    let seed = Seed::try_from("abc").unwrap();
+    let greed = GreedBuilder::new().seed(seed.clone()).build();
+    let impossible = Direction::ALL_DIRECTIONS_CW
+      .into_iter()
+      .find(|&dir| greed.check_move(dir).is_err());
+    if let Some(dir) = impossible {
+      let share_code = ShareCode::new(seed, alloc::vec![dir], None).unwrap();
+      assert!(share_code.build_greed().is_err());
+    }
+  }
+}
+
+mod replay_signature_test {
+  use super::*;
+
+  const KEY: &[u8] = b"leaderboard key";
+
+  fn signed_meta() -> GameMeta {
+    let greed = Greed::load_from_string(include_str!("test_data/played_game.json")).unwrap();
+    let signed = greed.save_to_string_signed(KEY);
+    serde_json::from_str(&signed).unwrap()
+  }
+
+  #[test]
+  fn test_verify() {
+    let game_meta = signed_meta();
+    assert_eq!(game_meta.verify_signature(KEY), Ok(()));
+    assert_eq!(
+      game_meta.verify_signature(b"other key"),
+      Err(SignatureError::SignatureMismatch)
+    );
+  }
+
+  #[test]
+  fn test_long_key() {
+    let key = [7; 100];
+    let mut game_meta = signed_meta();
+    game_meta.sign(&key);
+    assert_eq!(game_meta.verify_signature(&key), Ok(()));
+    assert_eq!(
+      game_meta.verify_signature(&key[..99]),
+      Err(SignatureError::SignatureMismatch)
+    );
+  }
+
+  #[test]
+  fn test_rejects_edits() {
+    let game_meta = signed_meta();
+    let mut edits = alloc::vec![game_meta.clone(); 6];
+    edits[0].score = edits[0].score.map(|score| score + 1);
+    edits[1].moves.as_mut().unwrap().pop();
+    edits[2].move_timestamps_ms.as_mut().unwrap()[0] -= 1000;
+    edits[3].time_spent_ms /= 2;
+    edits[4].moves.as_mut().unwrap().swap(0, 1);
+    edits[5].challenge = Some(Challenge::daily(
+      chrono::NaiveDate::from_ymd_opt(2026, 10, 17).unwrap(),
+    ));
+    for edited in edits {
+      assert_eq!(
+        edited.verify_signature(KEY),
+        Err(SignatureError::SignatureMismatch)
+      );
+    }
+  }
+
+  #[test]
+  fn test_ignores_recomputed_fields() {
+    let mut game_meta = signed_meta();
+    game_meta.human_score = None;
+    game_meta.file_version = None;
+    assert_eq!(game_meta.verify_signature(KEY), Ok(()));
+  }
+
+  #[test]
+  fn test_invalid_signature() {
+    let mut game_meta = signed_meta();
+    game_meta.signature = None;
+    assert_eq!(
+      game_meta.verify_signature(KEY),
+      Err(SignatureError::MissingSignature)
+    );
+    game_meta.signature = Some("xyz".into());
+    assert_eq!(
+      game_meta.verify_signature(KEY),
+      Err(SignatureError::MalformedSignature)
+    );
+    game_meta.signature = Some("abcd".into());
+    assert_eq!(
+      game_meta.verify_signature(KEY),
+      Err(SignatureError::SignatureMismatch)
+    );
+  }
+
+  #[test]
+  fn test_hmac_known_answer() {
+    // RFC 4231 test case 2
+    let mac = replay_signature::hmac_sha256(b"Jefe", b"what do ya want for nothing?");
+    assert_eq!(
+      alloc::format!("{mac:02x?}").replace(['[', ']', ',', ' '], ""),
+      "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+    );
+  }
+}
+
+mod topology_test {
+  use alloc::sync::Arc;
+
+  use super::*;
+
+  fn topology_game_state(topology: Topology) -> GameState {
+    let game_field = GameField::try_from("2@11\n")
+      .unwrap()
+      .with_topology(topology);
+    GameState::new(Arc::new(game_field))
+  }
+
+  fn torus_greed() -> Greed {
+    let mut greed = GreedBuilder::new()
+      .seed(Seed::try_from("torus#8x5##torus").unwrap())
+      .build();
+    let dir = Direction::ALL_DIRECTIONS_CW
+      .into_iter()
+      .find(|&dir| greed.check_move(dir).is_ok())
+      .unwrap();
+    greed.move_(dir).unwrap();
+    greed
+  }
+
+  #[test]
+  fn test_bounded_blocks_at_edge() {
+    let game_state = topology_game_state(Topology::Bounded);
+    assert_eq!(
+      game_state.check_move(Direction::LEFT),
+      Err(PlayableError::BadMove)
+    );
+  }
+
+  #[test]
+  fn test_torus_wraps_around() {
+    let mut game_state = topology_game_state(Topology::Torus);
+    assert_eq!(game_state.move_(Direction::LEFT), Ok(alloc::vec![0, 3]));
+    assert_eq!(game_state.player_pos(), Pos::new(3, 0));
+    assert_eq!(
+      game_state.neighbor(Pos::new(3, 0), Direction::UP | Direction::RIGHT),
+      Some(Pos::new(0, 0))
+    );
+  }
+
+  #[test]
+  fn test_torus_undo_wraps_back() {
+    let mut game_state = topology_game_state(Topology::Torus);
+    let initial = game_state.clone();
+    game_state.move_(Direction::LEFT).unwrap();
+    game_state.undo_move().unwrap();
+    assert_eq!(game_state.player_pos(), initial.player_pos());
+    assert_eq!(game_state.to_game_field(), initial.to_game_field());
+  }
+
+  #[test]
+  fn test_seed_topology() {
+    let seed = Seed::try_from("abc#4x4##torus").unwrap();
+    assert_eq!(seed.topology(), Topology::Torus);
+    assert_eq!(alloc::format!("{seed}"), "abc#4x4##torus");
+    assert_eq!(GameField::from_seed(&seed).topology(), Topology::Torus);
+    assert_eq!(
+      Seed::try_from("abc#4x4").unwrap().topology(),
+      Topology::Bounded
+    );
+    assert_eq!(
+      Seed::try_from("abc#4x4##klein"),
+      Err(SeedConversionError::InvalidTopology {
+        source: TopologyConversionError {
+          found: "klein".into()
+        }
+      })
+    );
+  }
+
+  #[test]
+  fn test_save_keeps_topology() {
+    let greed = torus_greed();
+    let game_meta = greed.game_meta();
+    assert_eq!(game_meta.topology, Some(Topology::Torus));
+    for loaded in [
+      Greed::load_from_string(&greed.save_to_string()).unwrap(),
+      Greed::load_from_bytes(&greed.save_to_bytes().unwrap()).unwrap(),
+    ] {
+      assert_eq!(loaded.topology(), Topology::Torus);
+      assert_eq!(loaded.game_state(), greed.game_state());
+      assert_eq!(loaded.validate_reproducibility(), Ok(()));
+    }
+  }
+
+  #[test]
+  fn test_wrong_topology() {
+    let mut game_meta = torus_greed().game_meta();
+    game_meta.topology = Some(Topology::Bounded);
+    game_meta.moves = None;
+    game_meta.last_game_field = None;
+    let greed = Greed::load_from_game_meta(game_meta).unwrap();
+    assert_eq!(
+      greed.validate_reproducibility(),
+      Err(ReproductionError::WrongTopology {
+        seed_topology: Topology::Torus,
+        game_field_topology: Topology::Bounded,
+      })
+    );
+  }
+}
+
+mod wall_test {
+  use alloc::format;
+
+  use super::*;
+
+  #[test]
+  fn test_parse_wall() {
+    let game_field = GameField::try_from("@1#\n#2#\n").unwrap();
+    assert_eq!(game_field.get(Pos::new(2, 0)), Some(Tile::Wall));
+    assert_eq!(format!("{game_field}"), "@1#\n#2#\n");
+    assert_eq!(Tile::try_from('#'), Ok(Tile::Wall));
+    assert_eq!(Tile::Wall.amount(), None);
+  }
+
+  #[test]
+  fn test_wall_blocks() {
+    assert_eq!(
+      game_state("@#1\n").check_move(Direction::RIGHT),
+      Err(PlayableError::BadMove)
+    );
+    assert_eq!(
+      game_state("@2#\n").check_move(Direction::RIGHT),
+      Err(PlayableError::BadMove)
+    );
+  }
+
+  #[test]
+  fn test_wall_never_scores() {
+    let mut game_state = game_state("@1#\n");
+    game_state.move_(Direction::RIGHT).unwrap();
+    assert_eq!(game_state.score(), 1);
+    assert_eq!(game_state.get(2), Some(Tile::Wall));
+    assert_eq!(Reachability::new(&game_state).dead_count(), 0);
+  }
+
+  #[test]
+  fn test_serde_wall() {
+    let game_field = GameField::try_from("@1#\n").unwrap();
+    let json = serde_json::to_string(&game_field).unwrap();
+    assert_eq!(
+      serde_json::from_str::<GameField>(&json).unwrap(),
+      game_field
+    );
+    assert_eq!(serde_json::to_string(&Tile::Wall).unwrap(), "\"#\"");
+
+    let greed = Greed::load_from_string("@1#\n2#3\n").unwrap();
+    let loaded = Greed::load_from_bytes(&greed.save_to_bytes().unwrap()).unwrap();
+    assert_eq!(loaded.game_field(), greed.game_field());
+  }
+
+  #[test]
+  fn test_seed_wall_probability() {
+    let seed = Seed::try_from("walls#8x8#01010101010101010102").unwrap();
+    assert_eq!(seed.tile_probabilities().wall(), 2);
+    assert_eq!(seed.tile_probabilities().tile_weights(), [1; 9]);
+    assert_eq!(format!("{seed}"), "walls#8x8#01010101010101010102");
+    let game_field = GameField::from_seed(&seed);
+    assert!(game_field.iter().any(Tile::is_wall));
+
+    let seed = Seed::try_from("walls#8x8").unwrap();
+    assert_eq!(seed.tile_probabilities().wall(), 0);
+    assert!(!GameField::from_seed(&seed).iter().any(Tile::is_wall));
+  }
+}
+
+mod playable_area_test {
+  use alloc::{format, sync::Arc};
+
+  use super::*;
+
+  const CROSS: &str = ".1.\n1@1\n.1.\n";
+
+  #[test]
+  fn test_parse_non_rectangular() {
+    let game_field = GameField::try_from(CROSS).unwrap();
+    assert_eq!(game_field.cell_count(), 9);
+    assert_eq!(game_field.tile_count(), 5);
+    assert!(!game_field.is_playable(Pos::new(0, 0)));
+    assert!(game_field.is_playable(Pos::new(1, 0)));
+    assert_eq!(format!("{game_field}"), CROSS);
+
+    // Off-board cells have to be written out
+    assert_eq!(
+      GameField::try_from("1@1\n11\n"),
+      Err(GameFieldParserError::NotRectangular)
+    );
+    let game_field = GameField::try_from("1@1\n11.\n").unwrap();
+    assert_eq!(game_field.tile_count(), 5);
+  }
+
+  #[test]
+  fn test_off_board_is_out_of_bounds() {
+    let game_state = game_state(".@1\n");
+    assert_eq!(
+      game_state.check_move(Direction::LEFT),
+      Err(PlayableError::BadMove)
+    );
+    let game_field = GameField::try_from("1@1.\n")
+      .unwrap()
+      .with_topology(Topology::Torus);
+    let mut game_state = GameState::new(Arc::new(game_field));
+    game_state.move_(Direction::RIGHT).unwrap();
+    assert_eq!(
+      game_state.check_move(Direction::RIGHT),
+      Err(PlayableError::BadMove)
+    );
+  }
+
+  #[test]
+  fn test_score_excludes_off_board() {
+    let mut greed = Greed::load_from_string(CROSS).unwrap();
+    assert_eq!(greed.score(), 0);
+    greed.move_(Direction::UP).unwrap();
+    assert_eq!(greed.score(), 1);
+    // Every tile except the starting position, the off-board corners are never counted
+    assert_eq!(greed.reachability().max_score(), 4);
+  }
+
+  #[test]
+  fn test_serialize_off_board() {
+    let game_field = GameField::try_from(CROSS).unwrap();
+    let json = serde_json::to_string(&game_field).unwrap();
+    assert!(json.contains("\"off_board\":[0,2,6,8]"));
+    assert_eq!(
+      serde_json::from_str::<GameField>(&json).unwrap(),
+      game_field
+    );
+
+    let rectangular = serde_json::to_string(&GameField::try_from("@1\n").unwrap()).unwrap();
+    assert!(!rectangular.contains("off_board"));
+
+    let invalid = "{\"vec\":[0,1],\"size\":[2,1],\"player_pos\":[0,0],\"off_board\":[0]}";
+    assert!(serde_json::from_str::<GameField>(invalid).is_err());
+  }
+
+  #[test]
+  fn test_save_off_board() {
+    let mut greed = Greed::load_from_string(CROSS).unwrap();
+    greed.move_(Direction::RIGHT).unwrap();
+    for loaded in [
+      Greed::load_from_string(&greed.save_to_string()).unwrap(),
+      Greed::load_from_bytes(&greed.save_to_bytes().unwrap()).unwrap(),
+    ] {
+      assert_eq!(loaded.game_field(), greed.game_field());
+      assert_eq!(loaded.game_state(), greed.game_state());
+      assert_eq!(loaded.tile_count(), 5);
+    }
+  }
+}
+
+mod rule_set_test {
+  use alloc::sync::Arc;
+
+  use super::*;
+
+  fn rule_set_game_state(str: &str, rule_set: RuleSet) -> GameState {
+    GameState::new(Arc::new(
+      GameField::try_from(str).unwrap().with_rule_set(rule_set),
+    ))
+  }
+
+  #[test]
+  fn test_orthogonal() {
+    let game_state = rule_set_game_state("@5\n51\n", RuleSet::ORTHOGONAL);
+    assert_eq!(
+      game_state.check_move(Direction::DOWN | Direction::RIGHT),
+      Err(PlayableError::DirectionNotAllowed)
+    );
+    assert!(game_state.is_game_complete());
+    assert!(!rule_set_game_state("@5\n51\n", RuleSet::EIGHT_WAY).is_game_complete());
+  }
+
+  #[test]
+  fn test_knight() {
+    let initial = rule_set_game_state("@000\n0010\n", RuleSet::KNIGHT);
+    let mut game_state = initial.clone();
+    assert_eq!(game_state.move_(Direction::RIGHT).unwrap(), [6]);
+    assert_eq!(game_state.player_pos(), Pos::new(2, 1));
+    game_state.undo_move().unwrap();
+    assert_eq!(game_state.player_pos(), Pos::new(0, 0));
+    assert_eq!(game_state.mask(), initial.mask());
+  }
+
+  #[test]
+  fn test_travel_distance() {
+    let last_tile = RuleSet::EIGHT_WAY.with_travel_distance(TravelDistance::LastTile);
+    assert_eq!(
+      rule_set_game_state("@313\n", last_tile).check_move(Direction::RIGHT),
+      Ok(alloc::vec![1, 2, 3])
+    );
+    assert_eq!(
+      rule_set_game_state("@231\n", last_tile).check_move(Direction::RIGHT),
+      Err(PlayableError::BadMove)
+    );
+
+    let sum_mod_9 = RuleSet::EIGHT_WAY.with_travel_distance(TravelDistance::SumMod9);
+    let mut game_state = rule_set_game_state("@291\n", sum_mod_9);
+    assert_eq!(game_state.move_(Direction::RIGHT), Ok(alloc::vec![1, 2]));
+    game_state.undo_move().unwrap();
+    assert_eq!(game_state.player_pos(), Pos::new(0, 0));
+  }
+
+  #[test]
+  fn test_builder_and_saves() {
+    let mut builder = GreedBuilder::new();
+    builder.rule_set(RuleSet::ORTHOGONAL);
+    let greed = builder.seed(Seed::try_from("rules#5x5").unwrap()).build();
+    assert_eq!(greed.rule_set(), RuleSet::ORTHOGONAL);
+
+    let rule_set = RuleSet::KNIGHT.with_travel_distance(TravelDistance::SumMod9);
+    let greed = Greed::load_from_game_meta(GameMeta {
+      rule_set: Some(rule_set),
+      ..greed.game_meta()
+    })
+    .unwrap();
+    for loaded in [
+      Greed::load_from_string(&greed.save_to_string()).unwrap(),
+      Greed::load_from_bytes(&greed.save_to_bytes().unwrap()).unwrap(),
+    ] {
+      assert_eq!(loaded.rule_set(), rule_set);
+    }
+    assert_eq!(
+      Greed::load_from_string("@1\n").unwrap().rule_set(),
+      RuleSet::EIGHT_WAY
+    );
+  }
+
+  #[test]
+  fn test_invalid_vectors() {
+    let huge = Pos::new(isize::MAX, 0);
+    let mut vectors = *RuleSet::ORTHOGONAL.vectors();
+    vectors[2] = Some(huge);
+    assert_eq!(
+      RuleSet::try_custom(vectors),
+      Err(RuleSetConversionError { vector: huge })
+    );
+    let mut zero = *RuleSet::ORTHOGONAL.vectors();
+    zero[0] = Some(Pos::new(0, 0));
+    assert!(RuleSet::try_custom(zero).is_err());
+
+    let game_meta = GameMeta {
+      initial_game_field: Some(GameField::try_from("1@1\n").unwrap()),
+      rule_set: Some(RuleSet::custom(vectors)),
+      ..Default::default()
+    };
+    assert!(matches!(
+      Greed::load_from_string(&serde_json::to_string(&game_meta).unwrap()),
+      Err(GreedParserError::InvalidMetaDataFromat { .. })
+    ));
+    assert_eq!(
+      GameMeta::from_bytes(&game_meta.to_bytes().unwrap()),
+      Err(BinaryFormatError::InvalidRuleSet {
+        source: RuleSetConversionError { vector: huge }
+      })
+    );
+
+    // Unchecked vectors don't overflow
+    let game_state = rule_set_game_state("1@1\n", RuleSet::custom(vectors));
+    assert_eq!(
+      game_state.check_move(Direction::RIGHT),
+      Err(PlayableError::BadMove)
+    );
+  }
+}
+
+mod multiplayer_test {
+  use alloc::{format, string::String, vec};
+
+  use super::*;
+
+  #[test]
+  fn test_turns_and_scores() {
+    let mut game = MultiplayerGame::try_from("A11B\n").unwrap();
+    assert_eq!(game.player_count(), 2);
+    assert_eq!(game.current_player(), 0);
+    game.move_(Direction::RIGHT).unwrap();
+    assert_eq!(game.current_player(), 1);
+    // Players block each other like empty tiles
+    assert_eq!(
+      game.check_move(Direction::RIGHT),
+      Err(PlayableError::BadMove)
+    );
+    game.move_(Direction::LEFT).unwrap();
+    assert_eq!(game.score(0), Some(1));
+    assert_eq!(game.score(1), Some(1));
+    assert_eq!(game.leaders(), [0, 1]);
+    assert!(game.is_game_complete());
+    assert_eq!(game.current_player(), 1);
+    assert_eq!(format!("{game}"), " ab \n");
+
+    game.undo_move().unwrap();
+    assert_eq!(game.current_player(), 1);
+    assert_eq!(game.score(1), Some(0));
+    assert_eq!(format!("{game}"), " A1B\n");
+  }
+
+  #[test]
+  fn test_last_mover_stays_current() {
+    let mut game = MultiplayerGame::try_from("A1B\n").unwrap();
+    game.move_(Direction::RIGHT).unwrap();
+    assert!(game.is_game_complete());
+    assert_eq!(game.current_player(), 0);
+  }
+
+  #[test]
+  fn test_stuck_player_is_skipped() {
+    let game = MultiplayerGame::try_from("A 1B1\n").unwrap();
+    assert!(game.is_out(0));
+    assert_eq!(game.current_player(), 1);
+  }
+
+  #[test]
+  fn test_save() {
+    let mut game = MultiplayerGame::from_seed(&Seed::try_from("lunch#6x6").unwrap(), 3)
+      .unwrap()
+      .with_names(vec![
+        String::from("Ann"),
+        String::from("Bo"),
+        String::from("Cy"),
+      ])
+      .unwrap();
+    assert_eq!(game.player_count(), 3);
+    while let Some(dir) = Direction::ALL_DIRECTIONS_CW
+      .into_iter()
+      .find(|&dir| game.check_move(dir).is_ok())
+    {
+      game.move_(dir).unwrap();
+    }
+    assert!(game.is_game_complete());
+    let loaded = MultiplayerGame::load_from_string(&game.save_to_string()).unwrap();
+    assert_eq!(loaded, game);
+    assert_eq!(loaded.name(1), Some("Bo"));
+  }
+
+  #[test]
+  fn test_seed_starts_avoid_walls() {
+    let seed = Seed::try_from("walls#8x8#01010101010101010110").unwrap();
+    let game_field = GameField::from_seed(&seed);
+    assert!(game_field.iter().filter(|tile| tile.is_wall()).count() > 32);
+    let game = MultiplayerGame::from_seed(&seed, 6).unwrap();
+    assert_eq!(game.player_pos(0), Some(game_field.player_pos()));
+    for player in 0..game.player_count() {
+      let pos = game.player_pos(player).unwrap();
+      assert!(!game_field.get(pos).unwrap().is_wall());
+    }
+  }
+
+  #[test]
+  fn test_invalid_players() {
+    assert!(matches!(
+      MultiplayerGame::try_from("A1A\n"),
+      Err(MultiplayerError::AmbiguousPlayer { player: 'A', .. })
+    ));
+    assert_eq!(
+      MultiplayerGame::try_from("A1C\n"),
+      Err(MultiplayerError::MissingPlayer { player: 'B' })
+    );
+    assert_eq!(
+      MultiplayerGame::try_from("A11\n"),
+      Err(MultiplayerError::PlayerCount { found: 1 })
+    );
+    assert_eq!(
+      MultiplayerGame::try_from(".A1b\n").map(|game| game.player_pos(1)),
+      Ok(Some(Pos::new(3, 0)))
+    );
+    assert_eq!(
+      MultiplayerGame::new(
+        GameField::try_from("@1.\n").unwrap(),
+        vec![Pos::new(0, 0), Pos::new(2, 0)]
+      ),
+      Err(MultiplayerError::InvalidStart {
+        player: 'B',
+        pos: Pos::new(2, 0)
+      })
+    );
+    assert_eq!(
+      MultiplayerGame::new(
+        GameField::try_from("@1#\n").unwrap(),
+        vec![Pos::new(0, 0), Pos::new(2, 0)]
+      ),
+      Err(MultiplayerError::InvalidStart {
+        player: 'B',
+        pos: Pos::new(2, 0)
+      })
+    );
+  }
+}
+
+mod time_attack_test {
+  use chrono::Duration;
+
+  use super::*;
+
+  fn greed() -> Greed {
+    GreedBuilder::new()
+      .seed(Seed::try_from("clock#6x6").unwrap())
+      .build()
+  }
+
+  fn any_move(greed: &Greed) -> Direction {
+    Direction::ALL_DIRECTIONS_CW
+      .into_iter()
+      .find(|&dir| greed.game_state().check_move(dir).is_ok())
+      .unwrap()
+  }
+
+  #[test]
+  fn test_pause_stops_the_clock() {
+    let mut greed = greed();
+    greed.pause();
+    assert!(greed.is_paused());
+    assert_eq!(greed.session_time(), Duration::zero());
+    let time_spent = greed.time_spent();
+    assert_eq!(greed.time_spent(), time_spent);
+
+    // Moving resumes the game
+    greed.move_(any_move(&greed)).unwrap();
+    assert!(!greed.is_paused());
+    greed.resume();
+    assert!(!greed.is_paused());
+  }
+
+  #[test]
+  fn test_time_up() {
+    let mut builder = GreedBuilder::new();
+    builder.time_limit(Duration::zero());
+    let mut greed = builder.seed(Seed::try_from("clock#6x6").unwrap()).build();
+    let dir = any_move(&greed);
+    assert!(greed.is_time_up());
+    assert!(greed.is_game_complete());
+    assert_eq!(greed.move_(dir), Err(PlayableError::TimeUp));
+    assert_eq!(greed.time_spent(), Duration::zero());
+    assert_eq!(greed.remaining_time(), Some(Duration::zero()));
+
+    greed.set_time_limit(None);
+    assert!(greed.move_(dir).is_ok());
+    assert_eq!(greed.remaining_time(), None);
+  }
+
+  #[test]
+  fn test_clock_is_saved() {
+    let mut greed = greed();
+    greed.set_time_limit(Some(Duration::minutes(3)));
+    greed.pause();
+    for loaded in [
+      Greed::load_from_string(&greed.save_to_string()).unwrap(),
+      Greed::load_from_bytes(&greed.save_to_bytes().unwrap()).unwrap(),
+    ] {
+      assert!(loaded.is_paused());
+      assert_eq!(loaded.time_limit(), Some(Duration::minutes(3)));
+      assert_eq!(
+        loaded.time_spent().num_milliseconds(),
+        greed.time_spent().num_milliseconds()
+      );
+    }
+
+    let game_meta = GameMeta {
+      idle_ms: 1500,
+      ..greed.game_meta()
+    };
+    let loaded = Greed::load_from_game_meta(game_meta).unwrap();
+    assert!(loaded.idle_time() >= Duration::milliseconds(1500));
+  }
+}
+
+mod clock_test {
+  use alloc::{sync::Arc, vec::Vec};
+
+  use chrono::{Duration, TimeZone, Utc};
+
+  use super::*;
+
+  fn greed(clock: &Arc<ManualClock>) -> Greed {
+    let mut builder = GreedBuilder::new();
+    builder.clock(SharedClock::from(clock.clone()));
+    builder.seed(Seed::try_from("clock#6x6").unwrap()).build()
+  }
+
+  #[test]
+  fn test_manual_clock() {
+    let start = Utc.timestamp_millis_opt(1_700_000_000_000).unwrap();
+    let clock = Arc::new(ManualClock::new(start));
+    let mut greed = greed(&clock);
+    assert_eq!(greed.time_spent(), Duration::zero());
+
+    clock.advance(Duration::seconds(10));
+    assert_eq!(greed.time_spent(), Duration::seconds(10));
+
+    greed.pause();
+    clock.advance(Duration::seconds(5));
+    assert_eq!(greed.time_spent(), Duration::seconds(10));
+    greed.resume();
+    assert_eq!(greed.idle_time(), Duration::seconds(5));
+
+    clock.set(start + Duration::seconds(20));
+    assert_eq!(greed.time_spent(), Duration::seconds(15));
+  }
+
+  #[test]
+  fn test_time_limit_with_manual_clock() {
+    let clock = Arc::new(ManualClock::default());
+    let mut greed = greed(&clock);
+    greed.set_time_limit(Some(Duration::seconds(30)));
+    assert!(!greed.is_time_up());
+
+    clock.advance(Duration::seconds(29));
+    assert_eq!(greed.remaining_time(), Some(Duration::seconds(1)));
+    assert!(!greed.is_game_complete());
+
+    clock.advance(Duration::seconds(1));
+    assert!(greed.is_time_up());
+    assert!(greed.is_game_complete());
+    assert_eq!(greed.move_(Direction::UP), Err(PlayableError::TimeUp));
+  }
+
+  #[test]
+  fn test_load_with_clock() {
+    let clock = Arc::new(ManualClock::default());
+    let mut greed = greed(&clock);
+    clock.advance(Duration::seconds(7));
+    let saved = greed.save_to_string();
+
+    clock.advance(Duration::hours(1));
+    let loaded =
+      Greed::load_from_string_with_clock(&saved, SharedClock::from(clock.clone())).unwrap();
+    assert_eq!(loaded.time_spent(), Duration::seconds(7));
+
+    // Loading starts a new session
+    clock.advance(Duration::seconds(3));
+    assert_eq!(loaded.time_spent(), Duration::seconds(10));
+    greed.pause();
+    assert_eq!(
+      greed.time_spent(),
+      Duration::hours(1) + Duration::seconds(10)
+    );
+  }
+
+  #[test]
+  fn test_load_bytes_with_clock() {
+    let clock = Arc::new(ManualClock::default());
+    let greed = greed(&clock);
+    clock.advance(Duration::seconds(7));
+    let bytes = greed.save_to_bytes().unwrap();
+
+    clock.advance(Duration::hours(1));
+    let loaded =
+      Greed::load_from_bytes_with_clock(&bytes, SharedClock::from(clock.clone())).unwrap();
+    clock.advance(Duration::seconds(3));
+    assert_eq!(loaded.time_spent(), Duration::seconds(10));
+  }
+
+  #[test]
+  fn test_share_code_with_clock() {
+    let clock = Arc::new(ManualClock::default());
+    let share_code =
+      ShareCode::new(Seed::try_from("clock#6x6").unwrap(), Vec::new(), None).unwrap();
+    let greed = share_code
+      .build_greed_with_clock(SharedClock::from(clock.clone()))
+      .unwrap();
+    clock.advance(Duration::seconds(10));
+    assert_eq!(greed.time_spent(), Duration::seconds(10));
+  }
+}
+
+mod challenge_test {
+  use alloc::{format, string::String};
+
+  use chrono::NaiveDate;
+
+  use super::*;
+
+  fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+  }
+
+  #[test]
+  fn test_challenge_ids() {
+    let daily = Challenge::daily(date(2026, 10, 17));
+    assert_eq!(format!("{daily}"), "daily-2026-10-17");
+    assert_eq!(daily.period(), ChallengePeriod::Daily);
+
+    // Every day of the week has the same weekly challenge
+    let weekly = Challenge::weekly(date(2026, 10, 17));
+    assert_eq!(format!("{weekly}"), "weekly-2026-W42");
+    assert_eq!(weekly.date(), date(2026, 10, 12));
+    assert_eq!(Challenge::weekly(date(2026, 10, 12)), weekly);
+
+    let salted = weekly.with_salt(UserString::try_from("team_7").unwrap());
+    assert_eq!(format!("{salted}"), "weekly-2026-W42@team_7");
+    assert_eq!(salted.salt(), Some("team_7"));
+
+    for challenge in [daily, salted] {
+      assert_eq!(Challenge::try_from(String::from(&challenge)), Ok(challenge));
+    }
+  }
+
+  #[test]
+  fn test_invalid_challenge_ids() {
+    for id in [
+      "",
+      "daily-2026-02-30",
+      "weekly-2026-W54",
+      "weekly-2026-W5",
+      "monthly-2026-10",
+    ] {
+      assert_eq!(
+        Challenge::try_from(id),
+        Err(ChallengeConversionError::InvalidFormat { found: id.into() })
+      );
+    }
+    assert_eq!(
+      Challenge::try_from("daily-2026-10-17@"),
+      Err(UserStringError::Empty.into())
+    );
+  }
+
+  #[test]
+  fn test_challenge_seeds() {
+    let daily = Challenge::daily(date(2026, 10, 17));
+    assert_eq!(daily.seed(), daily.seed());
+    assert_eq!(daily.seed().size(), Size2D::DEFAULT_SIZE);
+    assert_eq!(
+      daily.seed().tile_probabilities(),
+      &TileProbs::DEFAULT_TILE_PROBABILITIES
+    );
+    assert_ne!(Challenge::daily(date(2026, 10, 18)).seed(), daily.seed());
+    assert_ne!(
+      daily
+        .clone()
+        .with_salt(UserString::try_from("team").unwrap())
+        .seed(),
+      daily.seed()
+    );
+
+    // ISO week 42 uses the second preset
+    let weekly = Challenge::weekly(date(2026, 10, 17)).seed();
+    let (size, tile_probs) = &Challenge::WEEKLY_PRESETS[1];
+    assert_eq!(weekly.size(), *size);
+    assert_eq!(weekly.tile_probabilities(), tile_probs);
+    let next_week = Challenge::weekly(date(2026, 10, 19)).seed();
+    assert_eq!(next_week.size(), Challenge::WEEKLY_PRESETS[2].0);
+  }
+
+  #[test]
+  fn test_challenge_from_clock() {
+    use chrono::{TimeZone, Utc};
+
+    let clock = SharedClock::new(ManualClock::new(
+      Utc.with_ymd_and_hms(2026, 10, 17, 23, 59, 0).unwrap(),
+    ));
+    assert_eq!(
+      Challenge::today(&clock),
+      Challenge::daily(date(2026, 10, 17))
+    );
+    assert_eq!(
+      Challenge::this_week(&clock),
+      Challenge::weekly(date(2026, 10, 12))
+    );
+  }
+
+  #[test]
+  fn test_challenge_games() {
+    let challenge = Challenge::daily(date(2026, 10, 17));
+    let greed = GreedBuilder::new().challenge(challenge.clone()).build();
+    assert_eq!(greed.challenge(), Some(&challenge));
+    assert_eq!(greed.seed(), Some(&challenge.seed()));
+    assert_eq!(greed.name(), "daily-2026-10-17");
+
+    let saved = greed.save_to_string();
+    assert!(saved.contains("\"challenge\":\"daily-2026-10-17\""));
+    for loaded in [
+      Greed::load_from_string(&saved).unwrap(),
+      Greed::load_from_bytes(&greed.save_to_bytes().unwrap()).unwrap(),
+    ] {
+      assert_eq!(loaded.challenge(), Some(&challenge));
+    }
+
+    // Games without a challenge don't write the field
+    let greed = GreedBuilder::new().seed(challenge.seed()).build();
+    assert_eq!(greed.challenge(), None);
+    assert!(!greed.save_to_string().contains("challenge"));
+  }
+
+  #[test]
+  fn test_challenge_seed_mismatch() {
+    let challenge = Challenge::daily(date(2026, 10, 17));
+    let game_meta = GameMeta {
+      challenge: Some(challenge.clone()),
+      ..GreedBuilder::new()
+        .seed(Seed::try_from("other").unwrap())
+        .build()
+        .game_meta()
+    };
+    assert_eq!(
+      Greed::load_from_game_meta(game_meta),
+      Err(GreedParserError::ChallengeSeedMismatch { challenge })
+    );
+  }
+}
+
+mod board_generator_test {
+  use alloc::{format, sync::Arc, vec::Vec};
+
+  use rand::SeedableRng;
+
+  use super::*;
+
+  fn seed_with(generator: BoardGeneratorId) -> Seed {
+    Seed::try_from("boards#28x14")
+      .unwrap()
+      .with_generator(generator)
+  }
+
+  #[test]
+  fn test_uniform_is_unchanged() {
+    for (seed, board) in [
+      ("pinned#8x4", "78128765\n17417977\n49917453\n92@54463\n"),
+      (
+        "pinned#8x4#010203040506070809",
+        "89339877\n38628988\n69928675\n94@66685\n",
+      ),
+      (
+        "pinned#8x4###uniform",
+        "78128765\n17417977\n49917453\n92@54463\n",
+      ),
+    ] {
+      assert_eq!(
+        GameField::from_seed(&Seed::try_from(seed).unwrap()),
+        GameField::try_from(board).unwrap()
+      );
+    }
+  }
+
+  #[test]
+  fn test_seed_generator_format() {
+    let seed = Seed::try_from("abc#4fx15###clustered").unwrap();
+    assert_eq!(seed.generator(), BoardGeneratorId::Clustered);
+    assert_eq!(seed.topology(), Topology::Bounded);
+    assert_eq!(format!("{seed}"), "abc#4fx15###clustered");
+
+    let seed = Seed::try_from("abc#4fx15##torus#high_clearance").unwrap();
+    assert_eq!(seed.generator(), BoardGeneratorId::HighClearance);
+    assert_eq!(seed.topology(), Topology::Torus);
+    assert_eq!(format!("{seed}"), "abc#4fx15##torus#high_clearance");
+
+    // The default generator is never written
+    let seed = Seed::try_from("abc#4fx15##torus#uniform").unwrap();
+    assert_eq!(format!("{seed}"), "abc#4fx15##torus");
+    assert_eq!(
+      Seed::try_from("abc#4fx15###noise"),
+      Err(
+        BoardGeneratorIdConversionError {
+          found: "noise".into()
+        }
+        .into()
+      )
+    );
+    for generator in BoardGeneratorId::ALL {
+      assert_eq!(BoardGeneratorId::try_from(generator.name()), Ok(generator));
+    }
+  }
+
+  #[test]
+  fn test_generators_are_deterministic() {
+    for generator in BoardGeneratorId::ALL {
+      let seed = seed_with(generator);
+      let game_field = GameField::from_seed(&seed);
+      assert_eq!(game_field, GameField::from_seed(&seed));
+      assert_eq!(game_field.dimensions(), seed.size());
+      assert_eq!(game_field.get(game_field.player_pos()), Some(Tile::Player));
+    }
+  }
+
+  #[test]
+  fn test_clustered() {
+    let equal_neighbors = |game_field: &GameField| {
+      game_field
+        .iter()
+        .zip(game_field.iter().skip(1))
+        .filter(|(tile, next)| tile == next)
+        .count()
+    };
+    let uniform = GameField::from_seed(&seed_with(BoardGeneratorId::Uniform));
+    let clustered = GameField::from_seed(&seed_with(BoardGeneratorId::Clustered));
+    assert!(equal_neighbors(&clustered) > 2 * equal_neighbors(&uniform));
+  }
+
+  #[test]
+  fn test_symmetric() {
+    let game_field = GameField::from_seed(&seed_with(BoardGeneratorId::Symmetric));
+    let tiles: Vec<_> = game_field
+      .iter()
+      .map(|tile| if tile == Tile::Player { Tile::V0 } else { tile })
+      .collect();
+    assert!(tiles.iter().eq(tiles.iter().rev()));
+  }
+
+  #[test]
+  fn test_high_clearance_path() {
+    let size = Size2D::new(28, 14).unwrap();
+    for master_seed in 0..16 {
+      let mut rng = rand_pcg::Pcg64Mcg::seed_from_u64(master_seed);
+      let (game_field, path) = HighClearanceGenerator::generate_with_path(
+        size,
+        &TileProbs::DEFAULT_TILE_PROBABILITIES,
+        &mut rng,
+      );
+      let mut game_state = GameState::new(Arc::new(game_field));
+      for dir in path {
+        game_state.move_(dir).unwrap();
+      }
+      // Holds for these seeds, but isn't guaranteed for every seed
+      assert!(
+        game_state.score() * 100
+          >= size.tile_count() * HighClearanceGenerator::MIN_CLEARANCE_PERCENT
+      );
+    }
+  }
+}
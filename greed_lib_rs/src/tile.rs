@@ -22,6 +22,8 @@ pub enum Tile {
   V8,
   V9,
   Player,
+  /// Impassable, blocks moves like an empty tile but is never consumed.
+  Wall,
 }
 
 impl Serialize for Tile {
@@ -39,7 +41,7 @@ impl<'de> Visitor<'de> for TileVisitor {
   type Value = Tile;
 
   fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-    formatter.write_str("one of 0123456789 @")
+    formatter.write_str("one of 0123456789 @#")
   }
 
   fn visit_char<E>(self, v: char) -> Result<Self::Value, E>
@@ -64,7 +66,7 @@ impl Tile {
 
   #[must_use]
   pub fn amount(self) -> Option<u8> {
-    if self == Tile::Player {
+    if self == Tile::Player || self == Tile::Wall {
       None
     } else {
       Some(self as u8)
@@ -80,12 +82,19 @@ impl Tile {
   pub fn is_empty(self) -> bool {
     self == Tile::EMPTY
   }
+
+  #[must_use]
+  pub fn is_wall(self) -> bool {
+    self == Tile::Wall
+  }
 }
 
 impl From<Tile> for char {
   fn from(tile: Tile) -> Self {
     if let Some(amount) = tile.amount() {
       Self::from_digit(u32::from(amount), 10).unwrap()
+    } else if tile == Tile::Wall {
+      '#'
     } else {
       debug_assert_eq!(tile, Tile::Player);
       // since .amount() failed we must have a player tile, debug assert to make sure added tile types don't silently do something wrong
@@ -100,6 +109,7 @@ impl TryFrom<char> for Tile {
   fn try_from(value: char) -> Result<Self, Self::Error> {
     match value {
       '@' => Ok(Self::Player),
+      '#' => Ok(Self::Wall),
       ' ' => Ok(Self::V0),
       #[allow(clippy::cast_possible_truncation)] // Always is <= 9
       c => c
@@ -1,6 +1,6 @@
 use alloc::{fmt, string::String};
 
-use super::{Grid2D, Pos, Size2D, Tile};
+use super::{GameField, Grid2D, Pos, Size2D, Tile};
 use core::{
   fmt::{Formatter, Write},
   iter::FusedIterator,
@@ -191,7 +191,7 @@ pub trait TileGrid: TileGet<usize> + TileGet<Pos> + Grid2D {
   fn iter(&self) -> TileIterator<Self> {
     TileIterator {
       start: 0,
-      end: self.tile_count(),
+      end: self.cell_count(),
       grid: self,
     }
   }
@@ -208,7 +208,7 @@ pub trait TileGrid: TileGet<usize> + TileGet<Pos> + Grid2D {
   fn rows(&self) -> RowIterator<Self> {
     RowIterator {
       offset: 0,
-      end: self.tile_count(),
+      end: self.cell_count(),
       grid: self,
     }
   }
@@ -230,26 +230,37 @@ pub trait TileGrid: TileGet<usize> + TileGet<Pos> + Grid2D {
   /// # Errors
   /// If the formatter failed a write.
   fn display_fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-    for row in self.rows() {
-      for tile in row {
-        f.write_char(char::from(tile))?;
+    let x_size = self.dimensions().x_size;
+    for index in 0..self.cell_count() {
+      f.write_char(self.display_char(index))?;
+      if index % x_size == x_size - 1 {
+        f.write_char('\n')?;
       }
-      f.write_char('\n')?;
     }
     Ok(())
   }
 
+  /// The char of the tile at `index` or `GameField::OFF_BOARD_CHAR` for off-board cells.
+  fn display_char(&self, index: usize) -> char {
+    if self.is_playable(self.index_to_pos_unchecked(index)) {
+      char::from(self.get_unchecked(index))
+    } else {
+      GameField::OFF_BOARD_CHAR
+    }
+  }
+
   /// All types that implement `TileGrid` should also implement Display
   /// so you can alternatively call `to_string` which will usually end up calling this function.
   /// This name is a bit weird to avoid colliding with the `ToString` trait.
   fn to_string_tile_grid(&self) -> String {
     // Don't forget about the new line characters
-    let mut out = String::with_capacity(self.tile_count() + self.dimensions().y_size);
-    for row in self.rows() {
-      for tile in row {
-        out.push(char::from(tile));
+    let x_size = self.dimensions().x_size;
+    let mut out = String::with_capacity(self.cell_count() + self.dimensions().y_size);
+    for index in 0..self.cell_count() {
+      out.push(self.display_char(index));
+      if index % x_size == x_size - 1 {
+        out.push('\n');
       }
-      out.push('\n');
     }
     out
   }
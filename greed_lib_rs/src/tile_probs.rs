@@ -3,11 +3,41 @@ use thiserror::Error;
 
 type Inner = [u8; 9];
 
+/// Weights of the tiles 1..=9 and of walls. Iterating yields only the weights of 1..=9.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct TileProbs(Inner);
+pub struct TileProbs {
+  probs: Inner,
+  wall: u8,
+}
 
 impl TileProbs {
-  pub const DEFAULT_TILE_PROBABILITIES: TileProbs = TileProbs([1, 1, 1, 1, 1, 1, 1, 1, 1]);
+  pub const DEFAULT_TILE_PROBABILITIES: TileProbs = TileProbs {
+    probs: [1, 1, 1, 1, 1, 1, 1, 1, 1],
+    wall: 0,
+  };
+
+  /// `probs` must not be all zeros.
+  pub(super) const fn new_unchecked(probs: Inner) -> Self {
+    Self { probs, wall: 0 }
+  }
+
+  /// The weights of the tiles 1..=9 without the weight of walls. See `wall`.
+  #[must_use]
+  pub const fn tile_weights(&self) -> [u8; 9] {
+    self.probs
+  }
+
+  /// The weight of `Tile::Wall`. 0 by default.
+  #[must_use]
+  pub const fn wall(&self) -> u8 {
+    self.wall
+  }
+
+  #[must_use]
+  pub const fn with_wall(mut self, wall: u8) -> Self {
+    self.wall = wall;
+    self
+  }
 }
 
 impl IntoIterator for TileProbs {
@@ -15,7 +45,7 @@ impl IntoIterator for TileProbs {
   type IntoIter = <Inner as IntoIterator>::IntoIter;
 
   fn into_iter(self) -> Self::IntoIter {
-    self.0.into_iter()
+    self.probs.into_iter()
   }
 }
 impl<'a> IntoIterator for &'a TileProbs {
@@ -23,7 +53,7 @@ impl<'a> IntoIterator for &'a TileProbs {
   type IntoIter = <&'a Inner as IntoIterator>::IntoIter;
 
   fn into_iter(self) -> Self::IntoIter {
-    self.0.iter()
+    self.probs.iter()
   }
 }
 
@@ -37,7 +67,7 @@ pub enum TileProbsConversionError {
   AllZeros,
   #[error("Invalid Char in Tile Probabilities Format: {c}")]
   InvalidChar { c: char },
-  #[error("The wrong amount of chars was given ({count} given, expected 18")]
+  #[error("The wrong amount of chars was given ({count} given, expected 18 or 20 with walls)")]
   InvalidCharCount { count: usize },
 }
 
@@ -48,24 +78,26 @@ impl TryFrom<&str> for TileProbs {
     if let Some(c) = value.chars().find(|c| !c.is_ascii_hexdigit()) {
       return Err(TileProbsConversionError::InvalidChar { c });
     }
-    if value.len() != 2 * 9 {
+    if value.len() != 2 * 9 && value.len() != 2 * 10 {
       return Err(TileProbsConversionError::InvalidCharCount { count: value.len() });
     }
     let mut val_slices = value.as_bytes().chunks(2);
-    let mut vals = [0; 9];
-    for val in &mut vals {
+    let mut vals = [0; 10];
+    for val in &mut vals[..value.len() / 2] {
       let val_slice = val_slices.next().unwrap(); // since we checked the size previously this check is redundant
       let val_slice =
         core::str::from_utf8(val_slice).map_err(|_| TileProbsConversionError::InvalidFormat)?;
       *val =
         u8::from_str_radix(val_slice, 16).map_err(|_| TileProbsConversionError::InvalidFormat)?;
     }
-    Self::try_from(vals)
+    let [probs @ .., wall] = vals;
+    Ok(Self::try_from(probs)?.with_wall(wall))
   }
 }
+/// Same as `TileProbs::tile_weights`, the wall weight is dropped.
 impl From<TileProbs> for Inner {
   fn from(props: TileProbs) -> Self {
-    props.0
+    props.probs
   }
 }
 
@@ -76,12 +108,17 @@ impl TryFrom<Inner> for TileProbs {
     if value == [0; 9] {
       return Err(TileProbsConversionError::AllZeros);
     }
-    Ok(Self(value))
+    Ok(Self {
+      probs: value,
+      wall: 0,
+    })
   }
 }
 
 impl<'a> Arbitrary<'a> for TileProbs {
   fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
-    Self::try_from(<[u8; 9]>::arbitrary(u)?).map_err(|_| arbitrary::Error::IncorrectFormat)
+    let probs =
+      Self::try_from(<[u8; 9]>::arbitrary(u)?).map_err(|_| arbitrary::Error::IncorrectFormat)?;
+    Ok(probs.with_wall(u.arbitrary()?))
   }
 }
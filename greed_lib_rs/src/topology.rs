@@ -0,0 +1,67 @@
+use super::{Grid2D, Pos, Size2D};
+use alloc::string::String;
+use core::fmt::{self, Display, Formatter};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+#[error("Unknown topology ({found}) expected 'bounded' or 'torus'")]
+pub struct TopologyConversionError {
+  pub found: String,
+}
+
+/// Decides where a step off the edge of the board leads.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Topology {
+  /// Moving off an edge is not possible.
+  #[default]
+  Bounded,
+  /// Moving off an edge wraps around to the opposite edge.
+  Torus,
+}
+
+impl Topology {
+  /// The position `vector` away from `pos` or `None` if the step leaves the board.
+  /// `pos` must be valid for `size`.
+  #[must_use]
+  #[allow(clippy::cast_possible_wrap)] // The sizes in Size2D are limited
+  pub fn step(self, size: Size2D, pos: Pos, vector: Pos) -> Option<Pos> {
+    // Vectors of custom rule sets aren't checked
+    let next = Pos::new(pos.x.checked_add(vector.x)?, pos.y.checked_add(vector.y)?);
+    match self {
+      Self::Bounded => Some(next).filter(|&next| size.is_valid_pos(next)),
+      Self::Torus => Some(Pos::new(
+        next.x.rem_euclid(size.x_size as isize),
+        next.y.rem_euclid(size.y_size as isize),
+      )),
+    }
+  }
+
+  #[must_use]
+  pub const fn name(self) -> &'static str {
+    match self {
+      Self::Bounded => "bounded",
+      Self::Torus => "torus",
+    }
+  }
+}
+
+impl Display for Topology {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    f.write_str(self.name())
+  }
+}
+
+impl TryFrom<&str> for Topology {
+  type Error = TopologyConversionError;
+
+  fn try_from(value: &str) -> Result<Self, Self::Error> {
+    [Self::Bounded, Self::Torus]
+      .into_iter()
+      .find(|topology| topology.name() == value)
+      .ok_or_else(|| TopologyConversionError {
+        found: value.into(),
+      })
+  }
+}
@@ -2,7 +2,11 @@ mod utils;
 
 use std::convert::TryFrom;
 
-use greed_lib_rs::{Direction, Greed, GreedBuilder, Playable, Seed, Size2D, UserString};
+use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+use greed_lib_rs::{
+  Challenge, Clock, Direction, Greed, GreedBuilder, HintConfig, Playable, Seed, ShareCode,
+  SharedClock, Size2D, TileGrid, UserString,
+};
 use wasm_bindgen::prelude::*;
 
 // When the `wee_alloc` feature is enabled, use `wee_alloc` as the global
@@ -16,9 +20,42 @@ extern "C" {
   fn alert(s: &str);
 }
 
+/// Reads the time from the javascript `Date`.
+struct WasmClock;
+
+impl Clock for WasmClock {
+  fn now(&self) -> DateTime<Utc> {
+    #[allow(clippy::cast_possible_truncation)] // Milliseconds since the epoch fit into an i64
+    let now_ms = js_sys::Date::now() as i64;
+    Utc
+      .timestamp_millis_opt(now_ms)
+      .single()
+      .unwrap_or_default()
+  }
+  fn now_local(&self) -> DateTime<FixedOffset> {
+    // getTimezoneOffset is UTC - local in minutes
+    #[allow(clippy::cast_possible_truncation)]
+    let offset_minutes = js_sys::Date::new_0().get_timezone_offset() as i32;
+    FixedOffset::west_opt(offset_minutes * 60).map_or_else(
+      || self.now().fixed_offset(),
+      |offset| self.now().with_timezone(&offset),
+    )
+  }
+}
+
+fn wasm_clock() -> SharedClock {
+  SharedClock::new(WasmClock)
+}
+
+fn numpad_to_direction(key: u8) -> Option<Direction> {
+  Direction::from_numpad_digit(char::from_digit(key.into(), 10)?)
+}
+
 #[wasm_bindgen]
 pub struct Game {
   greed: Greed,
+  /// The score to beat if the game was loaded from a share code.
+  target_score: Option<usize>,
 }
 #[wasm_bindgen]
 pub fn set_panic_hook() {
@@ -29,7 +66,9 @@ pub fn set_panic_hook() {
 impl Game {
   pub fn from_string(value: &str) -> Result<Game, String> {
     Ok(Self {
-      greed: Greed::load_from_string(value).map_err(|err| format!("{err}"))?,
+      greed: Greed::load_from_string_with_clock(value, wasm_clock())
+        .map_err(|err| format!("{err}"))?,
+      target_score: None,
     })
   }
   pub fn generate(x_size: usize, y_size: usize, seed: &str) -> Result<Game, String> {
@@ -40,10 +79,57 @@ impl Game {
       let seed = UserString::try_from(seed).map_err(|err| format!("{err:?}"))?;
       Seed::new(seed, size, None)
     };
+    let mut builder = GreedBuilder::new();
+    builder.clock(wasm_clock());
+    Ok(Self {
+      greed: builder.seed(seed).build(),
+      target_score: None,
+    })
+  }
+  /// Today's board. Everyone with the same `salt` plays the same board.
+  pub fn daily_challenge(salt: &str) -> Result<Game, String> {
+    Self::challenge(Challenge::today(&wasm_clock()), salt)
+  }
+  /// This week's board. Everyone with the same `salt` plays the same board.
+  pub fn weekly_challenge(salt: &str) -> Result<Game, String> {
+    Self::challenge(Challenge::this_week(&wasm_clock()), salt)
+  }
+  fn challenge(challenge: Challenge, salt: &str) -> Result<Game, String> {
+    let challenge = if salt.is_empty() {
+      challenge
+    } else {
+      challenge.with_salt(UserString::try_from(salt).map_err(|err| format!("{err}"))?)
+    };
+    let mut builder = GreedBuilder::new();
+    builder.clock(wasm_clock());
     Ok(Self {
-      greed: GreedBuilder::new().seed(seed).build(),
+      greed: builder.challenge(challenge).build(),
+      target_score: None,
     })
   }
+  /// The id of the challenge or `None` if the game isn't part of one.
+  pub fn challenge_id(&self) -> Option<String> {
+    self.greed.challenge().map(String::from)
+  }
+  pub fn from_share_code(code: &str) -> Result<Game, String> {
+    let share_code = ShareCode::try_from(code).map_err(|err| format!("{err}"))?;
+    Ok(Self {
+      greed: share_code
+        .build_greed_with_clock(wasm_clock())
+        .map_err(|err| format!("{err}"))?,
+      target_score: share_code.target_score(),
+    })
+  }
+  /// Returns `None` for custom games without a seed.
+  pub fn share_code(&self) -> Option<String> {
+    ShareCode::from_greed(&self.greed).map(|share_code| share_code.to_string())
+  }
+  pub fn target_score(&self) -> Option<usize> {
+    self.target_score
+  }
+  pub fn score(&self) -> usize {
+    self.greed.score()
+  }
   pub fn print(&self) -> String {
     format!("{}", self.greed.game_state())
   }
@@ -54,16 +140,8 @@ impl Game {
       .map_or_else(|| "No Seed".into(), String::from)
   }
   pub fn move_numpad(&mut self, key: u8) -> Result<(), String> {
-    let dir = match key {
-      1 => Direction::DOWN.union(Direction::LEFT),
-      2 => Direction::DOWN,
-      3 => Direction::DOWN.union(Direction::RIGHT),
-      4 => Direction::LEFT,
-      6 => Direction::RIGHT,
-      7 => Direction::UP.union(Direction::LEFT),
-      8 => Direction::UP,
-      9 => Direction::UP.union(Direction::RIGHT),
-      _ => return Ok(()), // Fall through for cases like 0 and 5
+    let Some(dir) = numpad_to_direction(key) else {
+      return Ok(()); // Fall through for cases like 0 and 5
     };
     self
       .greed
@@ -72,6 +150,13 @@ impl Game {
       .map_err(|err| err.to_string())
   }
 
+  /// Returns the numpad key of the best move or `None` if no move is possible.
+  pub fn hint(&self) -> Option<u8> {
+    let best = self.greed.hints(&HintConfig::default()).first()?.dir;
+    let key = best.numpad_digit()?.to_digit(10)?;
+    u8::try_from(key).ok()
+  }
+
   pub fn undo(&mut self) -> Result<(), String> {
     self
       .greed
@@ -80,11 +165,27 @@ impl Game {
       .map_err(|err| err.to_string())
   }
   pub fn is_stuck(&mut self) -> bool {
-    Direction::ALL_DIRECTIONS_CW
-      .iter()
-      .all(|&dir| self.greed.check_move(dir).is_err())
+    self
+      .greed
+      .rule_set()
+      .directions()
+      .all(|dir| self.greed.check_move(dir).is_err())
   }
   pub fn save(&self) -> String {
     self.greed.save_to_string()
   }
+  /// Stops the clock while the game is hidden. Any move resumes it.
+  pub fn pause(&mut self) {
+    self.greed.pause();
+  }
+  pub fn resume(&mut self) {
+    self.greed.resume();
+  }
+  /// Milliseconds left in a timed game, `None` without a time limit.
+  pub fn remaining_ms(&self) -> Option<i64> {
+    self
+      .greed
+      .remaining_time()
+      .map(|remaining| remaining.num_milliseconds())
+  }
 }
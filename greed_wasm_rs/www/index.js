@@ -9,6 +9,7 @@ let y_size = document.getElementById("y_size");
 let seed = document.getElementById("seed");
 
 let save = document.getElementById("save_game");
+let share = document.getElementById("share_game");
 let load = document.getElementById("load_game");
 
 
@@ -34,7 +35,15 @@ save.onclick = (e) => {
         download(game.save(), "greed.json", "application/json")
     }
 }
+share.onclick = (e) => {
+    let code = game === undefined ? undefined : game.share_code();
+    if (code !== undefined) {
+        window.location.hash = code;
+        show_message(`Share this link: <b>${window.location.href}</b>`);
+    }
+}
 document.getElementById("gen_btn").onclick = generate;
+window.addEventListener("hashchange", load_share_code);
 
 document.addEventListener('keydown', (e) => {
 
@@ -57,7 +66,25 @@ document.addEventListener('keydown', (e) => {
     }
 });
 
-generate();
+if (window.location.hash.length > 1) {
+    load_share_code();
+} else {
+    generate();
+}
+
+function load_share_code() {
+    let code = decodeURIComponent(window.location.hash.slice(1));
+    if (game !== undefined && game.share_code() === code) {
+        return; // Our own share link
+    }
+    try {
+        game = Game.from_share_code(code);
+    } catch (e) {
+        out.innerHTML = `Error: <b>${e}</b>`;
+        game = undefined;
+    }
+    redraw();
+}
 
 function show_message(msg) {
     out.innerHTML = msg;
@@ -85,6 +112,10 @@ function redraw() {
         } else {
             out.innerHTML = `current seed: <b>${game.seed()}</b>`;
         }
+        let target_score = game.target_score();
+        if (target_score !== undefined) {
+            out.innerHTML += ` score: <b>${game.score()}</b> beat: <b>${target_score}</b>`;
+        }
     } else {
         game_field.innerText = "Invalid Game"
     }